@@ -0,0 +1,27 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! What the benchmarks of every authenticator share: the runtime [`Config`] they're run
+//! against, and the signed message with the longest encoding, which takes the most hashing to
+//! verify.
+
+use frame::prelude::{frame_system, Parameter};
+use traits_authn::{AuthorityId, Challenge};
+
+/// Runtimes benchmarking authenticators implement it once for all of them.
+pub trait Config: frame_system::Config {
+    /// The context of the challenges signed by devices.
+    type Context: Parameter + Copy + 'static;
+
+    /// The context with the longest encoding.
+    fn worst_case_context() -> Self::Context;
+}
+
+/// A message signed by devices, out of the parts every authenticator signs.
+pub trait SignedMessage<Cx> {
+    fn new(context: Cx, challenge: Challenge, authority_id: AuthorityId) -> Self;
+}
+
+/// The signed message with the longest encoding.
+pub fn worst_case_message<T: Config, M: SignedMessage<T::Context>>() -> M {
+    M::new(T::worst_case_context(), [0xff; 32], [0xff; 32])
+}
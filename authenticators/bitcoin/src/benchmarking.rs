@@ -0,0 +1,73 @@
+//! Benchmarks of the Bitcoin authenticator, recovering the signer of BIP-137 messages with the
+//! longest challenge context, and hashing its key (HASH160).
+
+use super::*;
+use authenticator_benchmarking::worst_case_message;
+pub use authenticator_benchmarking::Config;
+use btc::recover_btc_pubkey_hash;
+use frame::benchmarking::prelude::*;
+use sp_core::{ecdsa, Pair};
+use traits_authn::{util::VerifyCredential, DeviceChallengeResponse};
+
+pub struct Pallet<T: Config>(frame_system::Pallet<T>);
+
+impl<Cx> authenticator_benchmarking::SignedMessage<Cx> for SignedMessage<Cx> {
+    fn new(context: Cx, challenge: Challenge, authority_id: AuthorityId) -> Self {
+        SignedMessage {
+            context,
+            challenge,
+            authority_id,
+        }
+    }
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn register() {
+        let pair = ecdsa::Pair::from_seed(&[1; 32]);
+        let message = worst_case_message::<T, SignedMessage<_>>();
+        let signature = message.sign(&pair);
+        let pubkey_hash = recover_btc_pubkey_hash(&message.btc_message_hash(), &signature)
+            .expect("signature is recoverable");
+        let registration = BtcRegistration {
+            pubkey_hash,
+            message,
+            signature,
+        };
+
+        let valid;
+        #[block]
+        {
+            valid = registration.is_valid();
+        }
+
+        assert!(valid);
+    }
+
+    #[benchmark]
+    fn authenticate() {
+        let pair = ecdsa::Pair::from_seed(&[1; 32]);
+        let message = worst_case_message::<T, SignedMessage<_>>();
+        let signature = message.sign(&pair);
+        let mut pubkey_hash = recover_btc_pubkey_hash(&message.btc_message_hash(), &signature)
+            .expect("signature is recoverable");
+        let credential = BtcSignature {
+            user_id: [0xff; 32],
+            message,
+            signature,
+        };
+
+        let verified;
+        #[block]
+        {
+            verified = pubkey_hash.verify(&credential);
+        }
+
+        assert!(verified.is_some());
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -51,37 +51,71 @@ pub fn bitcoin_message_hash(message: &[u8]) -> [u8; 32] {
     sha2_256(&sha2_256(&data))
 }
 
-/// Recover a Bitcoin public key hash (HASH160) from a signed message.
+impl From<secp256k1_recovery::Error> for BtcError {
+    fn from(error: secp256k1_recovery::Error) -> Self {
+        match error {
+            secp256k1_recovery::Error::HighS => BtcError::HighS,
+            secp256k1_recovery::Error::BadRecoveryId => BtcError::BadRecoveryFlag,
+            secp256k1_recovery::Error::RecoveryFailed => BtcError::RecoveryFailed,
+        }
+    }
+}
+
+/// Recover a Bitcoin public key hash (HASH160) from a signed message. Signatures with a high
+/// `s` are rejected.
 ///
 /// The signature format follows BIP-137: recovery_flag[1] || r[32] || s[32]
 /// where recovery_flag encodes the recovery id and key compression.
 pub fn recover_btc_pubkey_hash(
     message_hash: &[u8; 32],
     signature: &[u8; 65],
-) -> Option<BtcPubkeyHash> {
+) -> Result<BtcPubkeyHash, BtcError> {
     // BIP-137: first byte is recovery flag
     // 27-30: uncompressed key, 31-34: compressed key
     let flag = signature[0];
     let (recovery_id, compressed) = match flag {
         27..=30 => (flag - 27, false),
         31..=34 => (flag - 31, true),
-        _ => return None,
+        _ => return Err(BtcError::BadRecoveryFlag),
     };
 
-    // Rearrange to sp_io format: r[32] || s[32] || recovery_id[1]
-    let mut sig = [0u8; 65];
-    sig[..64].copy_from_slice(&signature[1..65]);
-    sig[64] = recovery_id;
-
+    let sig = signature[1..].try_into().expect("64 bytes");
     if compressed {
-        let pubkey = sp_io::crypto::secp256k1_ecdsa_recover_compressed(&sig, message_hash).ok()?;
-        Some(BtcPubkeyHash::from_hash160(hash160(&pubkey)))
+        let pubkey = secp256k1_recovery::recover_compressed(sig, recovery_id, message_hash)?;
+        Ok(BtcPubkeyHash::from_hash160(hash160(&pubkey)))
     } else {
-        let pubkey = sp_io::crypto::secp256k1_ecdsa_recover(&sig, message_hash).ok()?;
+        let pubkey = secp256k1_recovery::recover(sig, recovery_id, message_hash)?;
         let mut uncompressed = [0u8; 65];
         uncompressed[0] = 0x04;
         uncompressed[1..].copy_from_slice(&pubkey);
-        Some(BtcPubkeyHash::from_hash160(hash160(&uncompressed)))
+        Ok(BtcPubkeyHash::from_hash160(hash160(&uncompressed)))
+    }
+}
+
+/// Verify that `message` is signed by the key hashing to `pubkey_hash`.
+fn verify_signature<Cx: Encode>(
+    pubkey_hash: &BtcPubkeyHash,
+    message: &SignedMessage<Cx>,
+    signature: &[u8; 65],
+) -> Result<(), BtcError> {
+    let recovered = recover_btc_pubkey_hash(&message.btc_message_hash(), signature)?;
+    if recovered != *pubkey_hash {
+        return Err(BtcError::PubkeyHashMismatch);
+    }
+    Ok(())
+}
+
+impl<Cx: Encode> BtcRegistration<Cx> {
+    /// Verify that the registration is signed by the key being registered.
+    pub fn verify(&self) -> Result<(), BtcError> {
+        verify_signature(&self.pubkey_hash, &self.message, &self.signature)
+    }
+}
+
+impl<Cx: Encode> BtcSignature<Cx> {
+    /// Verify that the credential is signed by the key hashing to `pubkey_hash`.
+    pub fn verify(&self, pubkey_hash: &BtcPubkeyHash) -> Result<(), BtcError> {
+        verify_signature(pubkey_hash, &self.message, &self.signature)
     }
 }
 
@@ -22,6 +22,11 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(feature = "runtime")]
+pub mod weights;
+
 #[cfg(feature = "runtime")]
 mod runtime {
     use super::*;
@@ -38,6 +43,8 @@ mod runtime {
 
 #[cfg(feature = "runtime")]
 pub use runtime::{Authenticator, Device};
+#[cfg(feature = "runtime")]
+pub use weights::WeightInfo;
 
 #[cfg(feature = "runtime")]
 mod btc;
@@ -65,6 +72,19 @@ impl BtcPubkeyHash {
     }
 }
 
+/// Why a Bitcoin signature failed to verify.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BtcError {
+    /// The BIP-137 recovery flag is not within 27 and 34.
+    BadRecoveryFlag,
+    /// `s` is in the upper half of the curve order, where signatures are malleable (BIP-62).
+    HighS,
+    /// No public key can be recovered from the signature.
+    RecoveryFailed,
+    /// The signature recovers a different public key hash.
+    PubkeyHashMismatch,
+}
+
 impl AsRef<DeviceId> for BtcPubkeyHash {
     fn as_ref(&self) -> &DeviceId {
         &self.0
@@ -1,5 +1,4 @@
 use super::*;
-use crate::btc::recover_btc_pubkey_hash;
 
 impl<Ch: Challenger, AuthId> From<BtcRegistration<CxOf<Ch>>> for Device<Ch, AuthId> {
     fn from(reg: BtcRegistration<CxOf<Ch>>) -> Self {
@@ -14,24 +13,9 @@ impl<Cx: Parameter + Encode + 'static> DeviceChallengeResponse<Cx> for BtcRegist
             "Verifying Bitcoin registration of {:?}",
             self.pubkey_hash,
         );
-        let hash = self.message.btc_message_hash();
-        match recover_btc_pubkey_hash(&hash, &self.signature) {
-            Some(recovered) => {
-                let valid = recovered == self.pubkey_hash;
-                if !valid {
-                    log::debug!(
-                        target: LOG_TARGET,
-                        "Pubkey hash mismatch: recovered {:?}, expected {:?}",
-                        recovered, self.pubkey_hash,
-                    );
-                }
-                valid
-            }
-            None => {
-                log::debug!(target: LOG_TARGET, "Failed to recover Bitcoin pubkey hash");
-                false
-            }
-        }
+        self.verify()
+            .inspect_err(|e| log::debug!(target: LOG_TARGET, "Invalid registration: {e:?}"))
+            .is_ok()
     }
 
     fn used_challenge(&self) -> (Cx, Challenge) {
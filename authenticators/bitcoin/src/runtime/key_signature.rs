@@ -1,5 +1,4 @@
 use super::*;
-use crate::btc::recover_btc_pubkey_hash;
 use traits_authn::UserChallengeResponse;
 
 impl<Cx: Parameter + Encode + 'static> UserChallengeResponse<Cx> for BtcSignature<Cx> {
@@ -27,8 +26,9 @@ impl<Cx: Encode> VerifyCredential<BtcSignature<Cx>> for BtcPubkeyHash {
             "Verifying Bitcoin signature for {:?}",
             self,
         );
-        let hash = credential.message.btc_message_hash();
-        let recovered = recover_btc_pubkey_hash(&hash, &credential.signature)?;
-        (recovered == *self).then_some(())
+        credential
+            .verify(self)
+            .inspect_err(|e| log::debug!(target: LOG_TARGET, "Invalid signature: {e:?}"))
+            .ok()
     }
 }
@@ -1,6 +1,7 @@
 use crate::mock::*;
 use crate::{
-    btc::recover_btc_pubkey_hash, BtcPubkeyHash, BtcRegistration, BtcSignature, SignedMessage,
+    btc::recover_btc_pubkey_hash, BtcError, BtcPubkeyHash, BtcRegistration, BtcSignature,
+    SignedMessage,
 };
 use frame::{
     deps::sp_core::{ecdsa, Pair},
@@ -224,7 +225,10 @@ mod btc_signing {
             sig[0] = 99; // Invalid flag
             sig[1..].copy_from_slice(&raw.0[..64]);
 
-            assert!(recover_btc_pubkey_hash(&hash, &sig).is_none());
+            assert_eq!(
+                recover_btc_pubkey_hash(&hash, &sig),
+                Err(BtcError::BadRecoveryFlag)
+            );
         })
     }
 
@@ -239,7 +243,10 @@ mod btc_signing {
             sig[0] = 0;
             sig[1..].copy_from_slice(&raw.0[..64]);
 
-            assert!(recover_btc_pubkey_hash(&hash, &sig).is_none());
+            assert_eq!(
+                recover_btc_pubkey_hash(&hash, &sig),
+                Err(BtcError::BadRecoveryFlag)
+            );
         })
     }
 
@@ -274,12 +281,33 @@ mod btc_signing {
         })
     }
 
+    #[test]
+    fn recover_rejects_high_s() {
+        new_test_ext().execute_with(|| {
+            let pair = BtcKey::get();
+            let hash = crate::btc::bitcoin_message_hash(b"hello bitcoin");
+            let raw = pair.sign_prehashed(&hash);
+
+            // The same signature, with n - s and the opposite recovery id
+            let mut sig = [0u8; 65];
+            sig[0] = 31 + (raw.0[64] ^ 1);
+            sig[1..].copy_from_slice(&secp256k1_recovery::complement_s(
+                raw.0[..64].try_into().unwrap(),
+            ));
+
+            assert_eq!(recover_btc_pubkey_hash(&hash, &sig), Err(BtcError::HighS));
+        })
+    }
+
     #[test]
     fn recover_fails_with_zero_signature() {
         new_test_ext().execute_with(|| {
             let hash = crate::btc::bitcoin_message_hash(b"test");
             let sig = [0u8; 65];
-            assert!(recover_btc_pubkey_hash(&hash, &sig).is_none());
+            assert_eq!(
+                recover_btc_pubkey_hash(&hash, &sig),
+                Err(BtcError::BadRecoveryFlag)
+            );
         })
     }
 
@@ -296,18 +324,74 @@ mod btc_signing {
                 let mut sig = [0u8; 65];
                 sig[0] = 31 + raw.0[64]; // Valid compressed flag
                 sig[1..].copy_from_slice(&raw.0[..64]);
-                assert!(recover_btc_pubkey_hash(&hash, &sig).is_some());
+                assert!(recover_btc_pubkey_hash(&hash, &sig).is_ok());
             }
 
             // Flag 35 must always be rejected
             let mut sig = [0u8; 65];
             sig[0] = 35;
             sig[1..].copy_from_slice(&raw.0[..64]);
-            assert!(recover_btc_pubkey_hash(&hash, &sig).is_none());
+            assert_eq!(
+                recover_btc_pubkey_hash(&hash, &sig),
+                Err(BtcError::BadRecoveryFlag)
+            );
 
             // Flag 26 must be rejected
             sig[0] = 26;
-            assert!(recover_btc_pubkey_hash(&hash, &sig).is_none());
+            assert_eq!(
+                recover_btc_pubkey_hash(&hash, &sig),
+                Err(BtcError::BadRecoveryFlag)
+            );
+        })
+    }
+}
+
+mod errors {
+    use super::*;
+
+    #[test]
+    fn registration_reports_pubkey_hash_mismatch() {
+        new_test_ext().execute_with(|| {
+            let (message, pubkey_hash, signature) = make_signature(&[]);
+
+            let registration = BtcRegistration {
+                pubkey_hash,
+                message,
+                signature,
+            };
+            assert_eq!(registration.verify(), Ok(()));
+
+            let registration = BtcRegistration {
+                pubkey_hash: BtcPubkeyHash::from_hash160([0xAB; 20]),
+                ..registration
+            };
+            assert_eq!(registration.verify(), Err(BtcError::PubkeyHashMismatch));
+        })
+    }
+
+    #[test]
+    fn signature_reports_failure_reason() {
+        new_test_ext().execute_with(|| {
+            let (message, pubkey_hash, signature) = make_signature(&[]);
+            let mut credential = BtcSignature {
+                user_id: USER,
+                message,
+                signature,
+            };
+            assert_eq!(credential.verify(&pubkey_hash), Ok(()));
+
+            // Signed over a different challenge
+            credential.message.challenge = [0u8; 32];
+            assert_eq!(
+                credential.verify(&pubkey_hash),
+                Err(BtcError::PubkeyHashMismatch)
+            );
+
+            credential.signature[0] = 26;
+            assert_eq!(
+                credential.verify(&pubkey_hash),
+                Err(BtcError::BadRecoveryFlag)
+            );
         })
     }
 }
@@ -0,0 +1,23 @@
+//! Weights of the Bitcoin authenticator, out of its `runtime-benchmarks` suite.
+
+use sp_runtime::Weight;
+
+/// Weight functions of the Bitcoin authenticator, so composite authenticators can charge the
+/// cost of each of their variants.
+pub trait WeightInfo {
+    /// Verifying a [`BtcRegistration`](crate::BtcRegistration).
+    fn register() -> Weight;
+    /// Verifying a [`BtcSignature`](crate::BtcSignature) against its device.
+    fn authenticate() -> Weight;
+}
+
+/// Estimates, until runtimes benchmark their own hardware.
+impl WeightInfo for () {
+    fn register() -> Weight {
+        Weight::from_parts(90_000_000, 0)
+    }
+
+    fn authenticate() -> Weight {
+        Weight::from_parts(90_000_000, 0)
+    }
+}
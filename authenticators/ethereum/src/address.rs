@@ -0,0 +1,132 @@
+use super::*;
+use core::{fmt, str::FromStr};
+use sha3::{Digest, Keccak256};
+
+/// Why a string is not an [`EthAddress`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParseAddressError {
+    /// The address doesn't start with `0x`.
+    MissingPrefix,
+    /// The address doesn't have 40 hex digits.
+    BadLength,
+    /// The address has characters other than hex digits.
+    InvalidHex,
+    /// The address is mixed-case, but not as its EIP-55 checksum.
+    BadChecksum,
+}
+
+impl fmt::Display for ParseAddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseAddressError::MissingPrefix => f.write_str("missing 0x prefix"),
+            ParseAddressError::BadLength => f.write_str("not 40 hex digits long"),
+            ParseAddressError::InvalidHex => f.write_str("invalid hex digit"),
+            ParseAddressError::BadChecksum => f.write_str("invalid EIP-55 checksum"),
+        }
+    }
+}
+
+impl core::error::Error for ParseAddressError {}
+
+impl EthAddress {
+    /// The 40 hex digits of the address, cased as per its EIP-55 checksum.
+    fn checksummed(&self) -> [u8; 40] {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+        let mut hex = [0u8; 40];
+        for (i, byte) in self.as_eth_bytes().iter().enumerate() {
+            hex[2 * i] = DIGITS[usize::from(byte >> 4)];
+            hex[2 * i + 1] = DIGITS[usize::from(byte & 0x0f)];
+        }
+
+        // Letters are uppercase where the nibble of the hash of the lowercase address is 8 or
+        // higher
+        let hash = Keccak256::digest(hex);
+        for (i, digit) in hex.iter_mut().enumerate() {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 {
+                digit.make_ascii_uppercase();
+            }
+        }
+        hex
+    }
+}
+
+/// Formats the 20-byte address with its EIP-55 checksum, e.g.
+/// `0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed`.
+impl fmt::Display for EthAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = self.checksummed();
+        f.write_str("0x")?;
+        f.write_str(core::str::from_utf8(&hex).expect("hex digits are ASCII"))
+    }
+}
+
+/// Parses a `0x`-prefixed address. Mixed-case addresses must match their EIP-55 checksum,
+/// while all-lowercase and all-uppercase ones carry none.
+impl FromStr for EthAddress {
+    type Err = ParseAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s
+            .strip_prefix("0x")
+            .ok_or(ParseAddressError::MissingPrefix)?
+            .as_bytes();
+        if hex.len() != 40 {
+            return Err(ParseAddressError::BadLength);
+        }
+
+        let nibble = |digit: u8| match digit {
+            b'0'..=b'9' => Ok(digit - b'0'),
+            b'a'..=b'f' => Ok(digit - b'a' + 10),
+            b'A'..=b'F' => Ok(digit - b'A' + 10),
+            _ => Err(ParseAddressError::InvalidHex),
+        };
+        let mut raw = [0u8; 20];
+        for (byte, digits) in raw.iter_mut().zip(hex.chunks(2)) {
+            *byte = (nibble(digits[0])? << 4) | nibble(digits[1])?;
+        }
+        let address = EthAddress::from_raw(raw);
+
+        let has_lowercase = hex.iter().any(u8::is_ascii_lowercase);
+        let has_uppercase = hex.iter().any(u8::is_ascii_uppercase);
+        if has_lowercase && has_uppercase && address.checksummed() != hex {
+            return Err(ParseAddressError::BadChecksum);
+        }
+        Ok(address)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::*;
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serializes as its [`Display`](fmt::Display) form.
+    impl Serialize for EthAddress {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    /// Deserializes from a string, as parsed by [`FromStr`].
+    impl<'de> Deserialize<'de> for EthAddress {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct AddressVisitor;
+
+            impl de::Visitor<'_> for AddressVisitor {
+                type Value = EthAddress;
+
+                fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.write_str("a 0x-prefixed Ethereum address")
+                }
+
+                fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                    v.parse()
+                        .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+                }
+            }
+
+            deserializer.deserialize_str(AddressVisitor)
+        }
+    }
+}
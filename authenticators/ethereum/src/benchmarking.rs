@@ -0,0 +1,144 @@
+//! Benchmarks of the Ethereum authenticator, recovering the signer of Sign-In with Ethereum
+//! messages, which take rendering and more hashing than the other modes, with the longest
+//! challenge context, chain ID and times.
+//!
+//! Signatures of [`ContractAuthenticator`] devices are benchmarked with the `()` oracle, so
+//! they're recovered like those of EOAs. Runtimes add the cost of asking their own oracle.
+
+use super::*;
+use authenticator_benchmarking::worst_case_message;
+pub use authenticator_benchmarking::Config;
+use frame::benchmarking::prelude::*;
+use sp_core::{ecdsa, Pair};
+use traits_authn::{util::VerifyCredential, DeviceChallengeResponse};
+
+const MODE: MessageMode = MessageMode::Siwe;
+
+pub struct Pallet<T: Config>(frame_system::Pallet<T>);
+
+/// Tells the chain ID and times with the longest renderings in messages, which expire at the
+/// last moment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LongestMessage;
+
+impl<Cx> crate::Config<Cx> for LongestMessage {
+    fn chain_id() -> Option<u64> {
+        Some(u64::MAX)
+    }
+
+    fn issued_at(_context: &Cx) -> Option<u64> {
+        Some(u64::MAX)
+    }
+
+    fn expiration_time(_context: &Cx) -> Option<u64> {
+        Some(u64::MAX)
+    }
+
+    fn unix_time() -> Option<u64> {
+        Some(u64::MAX - 1)
+    }
+}
+
+impl<Cx> authenticator_benchmarking::SignedMessage<Cx> for SignedMessage<Cx> {
+    fn new(context: Cx, challenge: Challenge, authority_id: AuthorityId) -> Self {
+        SignedMessage {
+            context,
+            challenge,
+            authority_id,
+        }
+    }
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn register() {
+        let pair = ecdsa::Pair::from_seed(&[1; 32]);
+        let message = worst_case_message::<T, SignedMessage<_>>();
+        let signature = message
+            .sign_with_mode::<LongestMessage>(&pair, MODE)
+            .expect("the message is valid");
+        let address = EthAddress::from_pair(&pair);
+        let registration =
+            EthRegistration::<_, LongestMessage>::new(address, message, MODE, signature);
+
+        let valid;
+        #[block]
+        {
+            valid = registration.is_valid();
+        }
+
+        assert!(valid);
+    }
+
+    #[benchmark]
+    fn authenticate() {
+        let pair = ecdsa::Pair::from_seed(&[1; 32]);
+        let message = worst_case_message::<T, SignedMessage<_>>();
+        let signature = message
+            .sign_with_mode::<LongestMessage>(&pair, MODE)
+            .expect("the message is valid");
+        let mut address = EthAddress::from_pair(&pair);
+        let credential =
+            EthSignature::<_, LongestMessage>::new([0xff; 32], message, MODE, signature);
+
+        let verified;
+        #[block]
+        {
+            verified = address.verify(&credential);
+        }
+
+        assert!(verified.is_some());
+    }
+
+    #[benchmark]
+    fn register_contract() {
+        let pair = ecdsa::Pair::from_seed(&[1; 32]);
+        let message = worst_case_message::<T, SignedMessage<_>>();
+        let signature = message
+            .sign_with_mode::<LongestMessage>(&pair, MODE)
+            .expect("the message is valid");
+        let registration = ContractRegistration::<_, (), LongestMessage>::new(
+            EthAddress::from_pair(&pair),
+            message,
+            MODE,
+            signature.to_vec().try_into().expect("signature fits"),
+        );
+
+        let valid;
+        #[block]
+        {
+            valid = registration.is_valid();
+        }
+
+        assert!(valid);
+    }
+
+    #[benchmark]
+    fn authenticate_contract() {
+        let pair = ecdsa::Pair::from_seed(&[1; 32]);
+        let message = worst_case_message::<T, SignedMessage<_>>();
+        let signature = message
+            .sign_with_mode::<LongestMessage>(&pair, MODE)
+            .expect("the message is valid");
+        let mut address = EthAddress::from_pair(&pair);
+        let credential = ContractSignature::<_, (), LongestMessage>::new(
+            [0xff; 32],
+            message,
+            MODE,
+            signature.to_vec().try_into().expect("signature fits"),
+        );
+
+        let verified;
+        #[block]
+        {
+            verified = address.verify(&credential);
+        }
+
+        assert!(verified.is_some());
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,35 @@
+/// What the runtime tells the Ethereum authenticator about the messages it verifies, which
+/// signers have no say on.
+///
+/// `()` tells nothing, so only [`MessageMode::PersonalSign`](crate::MessageMode::PersonalSign)
+/// messages, which aren't bound to a chain, verify with it.
+pub trait Config<Cx>: Clone + Eq + core::fmt::Debug + 'static {
+    /// The chain messages are signed for: the `chainId` of the EIP-712 domain of
+    /// [`TypedData`](crate::MessageMode::TypedData) messages, and the `Chain ID` of
+    /// [`Siwe`](crate::MessageMode::Siwe) ones. Signatures whose `v` encodes it as per EIP-155
+    /// are accepted too.
+    fn chain_id() -> Option<u64> {
+        None
+    }
+
+    /// When challenges of `context` are issued, in seconds since the Unix epoch (e.g. the time
+    /// of the block they're issued at): the `Issued At` of [`Siwe`](crate::MessageMode::Siwe)
+    /// messages, which don't verify without it.
+    fn issued_at(_context: &Cx) -> Option<u64> {
+        None
+    }
+
+    /// When challenges of `context` stop being valid, in seconds since the Unix epoch: the
+    /// `Expiration Time` of [`Siwe`](crate::MessageMode::Siwe) messages, if they have one.
+    fn expiration_time(_context: &Cx) -> Option<u64> {
+        None
+    }
+
+    /// The current time, in seconds since the Unix epoch, which messages must not have expired
+    /// at. `None` doesn't check expiration times.
+    fn unix_time() -> Option<u64> {
+        None
+    }
+}
+
+impl<Cx> Config<Cx> for () {}
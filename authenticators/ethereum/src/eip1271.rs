@@ -0,0 +1,160 @@
+use super::*;
+
+/// The suffix of EIP-6492 signatures: `0x6492` repeated to fill 32 bytes.
+const EIP6492_MAGIC_SUFFIX: [u8; 32] = [
+    0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92,
+    0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92,
+];
+
+/// Decides whether smart-contract wallets (e.g. Safe or Argent) accept signatures, as they
+/// would answer `isValidSignature` (EIP-1271) on their chain.
+///
+/// Runtimes implement it out of whatever they know about that chain, like bridged state or
+/// light-client proofs. `()` knows no contracts, so only EOAs verify with it.
+pub trait Eip1271Oracle: Clone + Eq + core::fmt::Debug + 'static {
+    /// Whether a contract is deployed at `address`. Only the contract decides on signatures
+    /// of its address, which are never taken for those of an EOA.
+    fn is_contract(address: &EthAddress) -> bool;
+
+    /// Whether `address` is a contract whose `isValidSignature(hash, signature)` returns the
+    /// EIP-1271 magic value (`0x1626ba7e`).
+    fn is_valid_signature(address: &EthAddress, hash: &[u8; 32], signature: &[u8]) -> bool;
+
+    /// Like [`is_valid_signature`](Self::is_valid_signature), for a wallet that is not deployed
+    /// yet, but would be at `address` by calling the factory of `counterfactual` (EIP-6492).
+    fn is_valid_counterfactual_signature(
+        _address: &EthAddress,
+        _hash: &[u8; 32],
+        _counterfactual: &Eip6492Signature,
+    ) -> bool {
+        false
+    }
+}
+
+impl Eip1271Oracle for () {
+    fn is_contract(_address: &EthAddress) -> bool {
+        false
+    }
+
+    fn is_valid_signature(_address: &EthAddress, _hash: &[u8; 32], _signature: &[u8]) -> bool {
+        false
+    }
+}
+
+/// The signature of a counterfactual wallet, unwrapped from its EIP-6492 encoding:
+/// `abi.encode(factory, factoryCalldata, signature) || magicSuffix`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Eip6492Signature<'a> {
+    /// The factory that deploys the wallet.
+    pub factory: [u8; 20],
+    /// The call to the factory that deploys the wallet.
+    pub factory_calldata: &'a [u8],
+    /// The EIP-1271 signature the wallet accepts once deployed.
+    pub signature: &'a [u8],
+}
+
+impl<'a> Eip6492Signature<'a> {
+    /// Unwraps `signature` if it ends with the EIP-6492 magic suffix, or returns `None`
+    /// otherwise.
+    pub fn parse(signature: &'a [u8]) -> Result<Option<Self>, EthError> {
+        let Some(encoded) = signature.strip_suffix(&EIP6492_MAGIC_SUFFIX) else {
+            return Ok(None);
+        };
+
+        let word = |offset: usize| encoded.get(offset..offset.checked_add(32)?);
+        let uint = |offset: usize| {
+            let word = word(offset)?;
+            word[..24]
+                .iter()
+                .all(|b| *b == 0)
+                .then(|| u64::from_be_bytes(word[24..].try_into().expect("8 bytes")))
+                .and_then(|uint| usize::try_from(uint).ok())
+        };
+        // Dynamic values are encoded at the offset in their head, as their length followed
+        // by their bytes.
+        let bytes = |head: usize| {
+            let offset = uint(head)?;
+            let start = offset.checked_add(32)?;
+            encoded.get(start..start.checked_add(uint(offset)?)?)
+        };
+
+        let factory = word(0)
+            .filter(|word| word[..12].iter().all(|b| *b == 0))
+            .map(|word| word[12..].try_into().expect("20 bytes"));
+        match (factory, bytes(32), bytes(64)) {
+            (Some(factory), Some(factory_calldata), Some(signature)) => Ok(Some(Self {
+                factory,
+                factory_calldata,
+                signature,
+            })),
+            _ => Err(EthError::MalformedEip6492Signature),
+        }
+    }
+}
+
+#[cfg(feature = "runtime")]
+mod verification {
+    use super::*;
+    use eth::recover_eth_address;
+
+    /// Verifies that `signature` of `hash` is accepted by `address`, whether it is a
+    /// counterfactual wallet, a deployed one, or an EOA, whose `v` may encode `chain_id` as
+    /// per EIP-155. Addresses are only taken for EOAs if the oracle knows no contract there.
+    pub fn verify_contract_signature<O: Eip1271Oracle>(
+        address: &EthAddress,
+        hash: &[u8; 32],
+        signature: &[u8],
+        chain_id: Option<u64>,
+    ) -> Result<(), EthError> {
+        if !address.is_well_formed() {
+            return Err(EthError::MalformedAddress);
+        }
+
+        if let Some(counterfactual) = Eip6492Signature::parse(signature)? {
+            return O::is_valid_counterfactual_signature(address, hash, &counterfactual)
+                .then_some(())
+                .ok_or(EthError::ContractSignatureRejected);
+        }
+        if O::is_contract(address) {
+            return O::is_valid_signature(address, hash, signature)
+                .then_some(())
+                .ok_or(EthError::ContractSignatureRejected);
+        }
+
+        // Not a contract the oracle knows, so it must be an EOA
+        let signature = signature
+            .try_into()
+            .map_err(|_| EthError::ContractSignatureRejected)?;
+        if recover_eth_address(hash, signature, chain_id)? != *address {
+            return Err(EthError::AddressMismatch);
+        }
+        Ok(())
+    }
+
+    impl<Cx: Encode, O: Eip1271Oracle, P: Config<Cx>> ContractRegistration<Cx, O, P> {
+        /// Verify that the registration is signed by the address being registered.
+        pub fn verify(&self) -> Result<(), EthError> {
+            verify_contract_signature::<O>(
+                &self.address,
+                &self.message.signing_hash::<P>(self.mode, &self.address)?,
+                &self.signature,
+                P::chain_id(),
+            )
+        }
+    }
+
+    impl<Cx: Encode, O: Eip1271Oracle, P: Config<Cx>> ContractSignature<Cx, O, P> {
+        /// Verify that the credential is signed by `address`.
+        pub fn verify(&self, address: &EthAddress) -> Result<(), EthError> {
+            verify_contract_signature::<O>(
+                address,
+                &self.message.signing_hash::<P>(self.mode, address)?,
+                &self.signature,
+                P::chain_id(),
+            )
+        }
+    }
+}
+
+#[cfg(feature = "runtime")]
+pub use verification::verify_contract_signature;
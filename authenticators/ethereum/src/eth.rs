@@ -1,8 +1,7 @@
 use super::*;
+use alloc::{format, string::String};
 use sp_io::hashing::keccak_256;
 
-extern crate alloc;
-
 impl<Cx: Encode> SignedMessage<Cx> {
     /// The domain-separated payload bytes.
     /// Prefixed with `b"ETH"` to prevent cross-authenticator signature replay.
@@ -22,43 +21,242 @@ impl<Cx: Encode> SignedMessage<Cx> {
         let payload = self.payload();
         personal_sign_hash(&payload)
     }
+
+    /// Compute the EIP-712 hash of the message as `SignedMessage` typed data, in the
+    /// `PalletPass` domain of `chain_id`.
+    /// Format: keccak256("\x19\x01" || domainSeparator || hashStruct(message))
+    pub fn eip712_hash(&self, chain_id: u64) -> [u8; 32] {
+        let mut chain_id_word = [0u8; 32];
+        chain_id_word[24..].copy_from_slice(&chain_id.to_be_bytes());
+        let domain_separator = keccak_256(
+            &[
+                keccak_256(EIP712_DOMAIN_TYPE),
+                keccak_256(EIP712_DOMAIN_NAME),
+                chain_id_word,
+                self.authority_id,
+            ]
+            .concat(),
+        );
+        let struct_hash = keccak_256(
+            &[
+                keccak_256(SIGNED_MESSAGE_TYPE),
+                keccak_256(&self.context.encode()),
+                self.challenge,
+                self.authority_id,
+            ]
+            .concat(),
+        );
+        keccak_256(&[&b"\x19\x01"[..], &domain_separator, &struct_hash].concat())
+    }
+
+    /// Render the message as an EIP-4361 (Sign-In with Ethereum) message of `signer`. The
+    /// domain is the authority, as text if it's a valid host, or hex otherwise, and the nonce
+    /// is the hex-encoded challenge.
+    pub fn siwe_message(
+        &self,
+        signer: &EthAddress,
+        chain_id: u64,
+        issued_at: u64,
+        expiration_time: Option<u64>,
+    ) -> String {
+        let domain = siwe_domain(&self.authority_id);
+        let mut message = format!(
+            "{domain} wants you to sign in with your Ethereum account:\n\
+            {signer}\n\n\
+            {SIWE_STATEMENT}\n\n\
+            URI: pass://{domain}\n\
+            Version: 1\n\
+            Chain ID: {chain_id}\n\
+            Nonce: {}\n\
+            Issued At: {}",
+            hex(&self.challenge),
+            rfc3339(issued_at),
+        );
+        if let Some(expiration_time) = expiration_time {
+            message += &format!("\nExpiration Time: {}", rfc3339(expiration_time));
+        }
+        message
+    }
+
+    /// The hash `signer` signs in `mode`, as told by `P`. Expired messages have none.
+    pub fn signing_hash<P: Config<Cx>>(
+        &self,
+        mode: MessageMode,
+        signer: &EthAddress,
+    ) -> Result<[u8; 32], EthError> {
+        let chain_id = || mode.chain_id::<Cx, P>().ok_or(EthError::ModeNotSupported);
+        Ok(match mode {
+            MessageMode::PersonalSign => self.eth_message_hash(),
+            MessageMode::TypedData => self.eip712_hash(chain_id()?),
+            MessageMode::Siwe => {
+                let issued_at = P::issued_at(&self.context).ok_or(EthError::ModeNotSupported)?;
+                let expiration_time = P::expiration_time(&self.context);
+                if expiration_time
+                    .zip(P::unix_time())
+                    .is_some_and(|(expiration_time, now)| now >= expiration_time)
+                {
+                    return Err(EthError::Expired);
+                }
+                personal_sign_hash(
+                    self.siwe_message(signer, chain_id()?, issued_at, expiration_time)
+                        .as_bytes(),
+                )
+            }
+        })
+    }
+}
+
+const EIP712_DOMAIN_TYPE: &[u8] = b"EIP712Domain(string name,uint256 chainId,bytes32 salt)";
+const EIP712_DOMAIN_NAME: &[u8] = b"PalletPass";
+const SIGNED_MESSAGE_TYPE: &[u8] =
+    b"SignedMessage(bytes context,bytes32 challenge,bytes32 authority)";
+
+const SIWE_STATEMENT: &str = "Sign in with your Ethereum account to use your Pass account.";
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// The authority as text if it's an ASCII host name (padded with zeros), or as hex otherwise.
+fn siwe_domain(authority: &AuthorityId) -> String {
+    let name = authority
+        .iter()
+        .rposition(|b| *b != 0)
+        .map_or(&authority[..0], |last| &authority[..=last]);
+    let is_host = !name.is_empty()
+        && name
+            .iter()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~'));
+    if is_host {
+        name.iter().map(|b| *b as char).collect()
+    } else {
+        hex(authority)
+    }
+}
+
+/// Format a Unix time (in seconds) as an RFC 3339 UTC date-time.
+fn rfc3339(time: u64) -> String {
+    let (days, secs) = (time / 86_400, time % 86_400);
+    // The civil date of days since 1970-01-01, counted in 400-year eras starting on March 1st
+    // (see https://howardhinnant.github.io/date_algorithms.html#civil_from_days)
+    let z = days + 719_468;
+    let (era, doe) = (z / 146_097, z % 146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = era * 400 + yoe + u64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs / 3_600,
+        secs / 60 % 60,
+        secs % 60
+    )
 }
 
 /// Hash a message using Ethereum's personal_sign format.
 /// `keccak256("\x19Ethereum Signed Message:\n{len}{message}")`
 pub fn personal_sign_hash(message: &[u8]) -> [u8; 32] {
-    let prefix = alloc::format!("\x19Ethereum Signed Message:\n{}", message.len());
+    let prefix = format!("\x19Ethereum Signed Message:\n{}", message.len());
     let mut data = prefix.into_bytes();
     data.extend_from_slice(message);
     keccak_256(&data)
 }
 
-/// Recover an Ethereum address from a personal_sign signature.
-pub fn recover_eth_address(message_hash: &[u8; 32], signature: &[u8; 65]) -> Option<EthAddress> {
+impl From<secp256k1_recovery::Error> for EthError {
+    fn from(error: secp256k1_recovery::Error) -> Self {
+        match error {
+            secp256k1_recovery::Error::HighS => EthError::HighS,
+            secp256k1_recovery::Error::BadRecoveryId => EthError::BadRecoveryId,
+            secp256k1_recovery::Error::RecoveryFailed => EthError::RecoveryFailed,
+        }
+    }
+}
+
+/// Recover an Ethereum address from a signature of a message signed for `chain_id` (if any).
+/// Signatures with a high `s` are rejected.
+pub fn recover_eth_address(
+    message_hash: &[u8; 32],
+    signature: &[u8; 65],
+    chain_id: Option<u64>,
+) -> Result<EthAddress, EthError> {
     // The signature format is r[32] || s[32] || v[1]
-    let mut sig = [0u8; 65];
-    sig[..64].copy_from_slice(&signature[..64]);
-    // Normalize v: Ethereum uses 27/28, secp256k1 recovery uses 0/1
-    sig[64] = match signature[64] {
-        v @ 0..=1 => v,
-        v @ 27..=28 => v - 27,
-        _ => return None,
-    };
+    let (rs, v) = signature.split_at(64);
+    let recovery_id = crate::signature::recovery_id(v[0], chain_id)?;
 
     // Recover uncompressed public key (64 bytes, without 0x04 prefix)
-    let pubkey = sp_io::crypto::secp256k1_ecdsa_recover(&sig, message_hash).ok()?;
+    let pubkey =
+        secp256k1_recovery::recover(rs.try_into().expect("64 bytes"), recovery_id, message_hash)?;
     // Ethereum address = last 20 bytes of keccak256(uncompressed_pubkey)
     let hash = keccak_256(&pubkey);
     let mut addr = [0u8; 20];
     addr.copy_from_slice(&hash[12..]);
-    Some(EthAddress::from_raw(addr))
+    Ok(EthAddress::from_raw(addr))
+}
+
+/// Verify that `message` is signed by `address`, as told by `P`.
+fn verify_signature<Cx: Encode, P: Config<Cx>>(
+    address: &EthAddress,
+    message: &SignedMessage<Cx>,
+    mode: MessageMode,
+    signature: &[u8; 65],
+) -> Result<(), EthError> {
+    if !address.is_well_formed() {
+        return Err(EthError::MalformedAddress);
+    }
+    let recovered = recover_eth_address(
+        &message.signing_hash::<P>(mode, address)?,
+        signature,
+        P::chain_id(),
+    )?;
+    if recovered != *address {
+        return Err(EthError::AddressMismatch);
+    }
+    Ok(())
+}
+
+impl<Cx: Encode, P: Config<Cx>> EthRegistration<Cx, P> {
+    /// Verify that the registration is signed by the address being registered.
+    pub fn verify(&self) -> Result<(), EthError> {
+        verify_signature::<_, P>(&self.address, &self.message, self.mode, &self.signature)
+    }
+}
+
+impl<Cx: Encode, P: Config<Cx>> EthSignature<Cx, P> {
+    /// Verify that the credential is signed by `address`.
+    pub fn verify(&self, address: &EthAddress) -> Result<(), EthError> {
+        verify_signature::<_, P>(address, &self.message, self.mode, &self.signature)
+    }
 }
 
 #[cfg(feature = "full-crypto")]
 impl<Cx: Encode> SignedMessage<Cx> {
     /// Sign the message with a secp256k1 key, producing a 65-byte Ethereum-style signature.
     pub fn sign(&self, pair: &sp_core::ecdsa::Pair) -> [u8; 65] {
-        let hash = self.eth_message_hash();
-        pair.sign_prehashed(&hash).0
+        self.sign_with_mode::<()>(pair, MessageMode::PersonalSign)
+            .expect("personal_sign messages aren't bound to a chain")
+    }
+
+    /// Sign the message in `mode` with a secp256k1 key, as told by `P`.
+    pub fn sign_with_mode<P: Config<Cx>>(
+        &self,
+        pair: &sp_core::ecdsa::Pair,
+        mode: MessageMode,
+    ) -> Result<[u8; 65], EthError> {
+        let hash = self.signing_hash::<P>(mode, &EthAddress::from_pair(pair))?;
+        normalize_signature(&pair.sign_prehashed(&hash).0, None)
+    }
+}
+
+#[cfg(feature = "full-crypto")]
+impl EthAddress {
+    /// The address of a secp256k1 key.
+    pub fn from_pair(pair: &sp_core::ecdsa::Pair) -> Self {
+        // Recovering is the way sp-core has to get the uncompressed public key
+        let hash = [0u8; 32];
+        normalize_signature(&pair.sign_prehashed(&hash).0, None)
+            .and_then(|signature| recover_eth_address(&hash, &signature, None))
+            .expect("signatures of a key recover its address")
     }
 }
@@ -2,16 +2,27 @@
 
 //! # Ethereum Authenticator for Pallet Pass
 //!
-//! Verifies Ethereum `personal_sign` signatures, enabling MetaMask,
-//! WalletConnect and other EVM wallets to authenticate with pallet-pass.
+//! Verifies Ethereum `personal_sign`, EIP-712 (`eth_signTypedData_v4`) and Sign-In with
+//! Ethereum (EIP-4361) signatures, enabling MetaMask, WalletConnect and other EVM wallets to
+//! authenticate with pallet-pass. What those last two bind messages to, like their chain and
+//! the times of Sign-In with Ethereum ones, is told by the runtime's [`Config`] rather than by
+//! signers.
+//!
+//! Smart-contract wallets are supported through [`ContractAuthenticator`], which asks an
+//! [`Eip1271Oracle`] provided by the runtime about their signatures (EIP-1271), including
+//! those of wallets not deployed yet (EIP-6492).
+
+extern crate alloc;
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, Input};
+use core::marker::PhantomData;
 use traits_authn::{AuthorityId, Challenge, DeviceId, HashedUserId};
 
 #[cfg(feature = "runtime")]
 use {
     codec::{DecodeWithMemTracking, MaxEncodedLen},
     scale_info::TypeInfo,
+    sp_runtime::{traits::ConstU32, BoundedVec},
 };
 
 #[cfg(test)]
@@ -19,6 +30,11 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(feature = "runtime")]
+pub mod weights;
+
 #[cfg(feature = "runtime")]
 mod runtime {
     use super::*;
@@ -29,18 +45,42 @@ mod runtime {
     mod key_signature;
 
     type CxOf<Ch> = <Ch as Challenger>::Context;
-    pub type Authenticator<Ch, AuthId> = Auth<Device<Ch, AuthId>, EthRegistration<CxOf<Ch>>>;
-    pub type Device<Ch, A> = Dev<EthAddress, A, Ch, EthSignature<CxOf<Ch>>>;
+    pub type Authenticator<Ch, AuthId, P = ()> =
+        Auth<Device<Ch, AuthId, P>, EthRegistration<CxOf<Ch>, P>>;
+    pub type Device<Ch, A, P = ()> = Dev<EthAddress, A, Ch, EthSignature<CxOf<Ch>, P>>;
+    pub type ContractAuthenticator<Ch, AuthId, O, P = ()> =
+        Auth<ContractDevice<Ch, AuthId, O, P>, ContractRegistration<CxOf<Ch>, O, P>>;
+    pub type ContractDevice<Ch, A, O, P = ()> =
+        Dev<EthAddress, A, Ch, ContractSignature<CxOf<Ch>, O, P>>;
 }
 
 #[cfg(feature = "runtime")]
-pub use runtime::{Authenticator, Device};
+pub use runtime::{Authenticator, ContractAuthenticator, ContractDevice, Device};
+#[cfg(feature = "runtime")]
+pub use weights::WeightInfo;
 
 #[cfg(feature = "runtime")]
 mod eth;
 
+mod address;
+pub use address::ParseAddressError;
+
+mod config;
+pub use config::Config;
+
+mod signature;
+pub use signature::normalize_signature;
+
+mod eip1271;
+#[cfg(feature = "runtime")]
+pub use eip1271::verify_contract_signature;
+pub use eip1271::{Eip1271Oracle, Eip6492Signature};
+
 /// A 20-byte Ethereum address stored in a 32-byte DeviceId-compatible container
 /// (left-padded with 12 zero bytes).
+///
+/// It is written as per EIP-55 (e.g. `0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed`) by its
+/// `Display`, `FromStr` and, with the `serde` feature, serde implementations.
 #[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, Debug)]
 #[cfg_attr(
     feature = "runtime",
@@ -67,6 +107,30 @@ impl EthAddress {
     }
 }
 
+/// Why an Ethereum signature failed to verify.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EthError {
+    /// The upper 12 padding bytes of the [`EthAddress`] are not zero.
+    MalformedAddress,
+    /// `v` is neither a recovery id (0 or 1), a legacy one (27 or 28), nor an EIP-155 one
+    /// of the chain the runtime's [`Config`] tells.
+    BadRecoveryId,
+    /// The runtime doesn't tell what the mode of the message binds it to, like its chain.
+    ModeNotSupported,
+    /// The message expired, as told by the runtime's [`Config`].
+    Expired,
+    /// `s` is in the upper half of the curve order, where signatures are malleable (EIP-2).
+    HighS,
+    /// No public key can be recovered from the signature.
+    RecoveryFailed,
+    /// The signature recovers a different address.
+    AddressMismatch,
+    /// The wallet contract rejects the signature, or it isn't one an EOA could make.
+    ContractSignatureRejected,
+    /// The signature ends with the EIP-6492 suffix, but isn't ABI-encoded as such.
+    MalformedEip6492Signature,
+}
+
 impl AsRef<DeviceId> for EthAddress {
     fn as_ref(&self) -> &DeviceId {
         &self.0
@@ -85,28 +149,251 @@ pub struct SignedMessage<Cx> {
     pub authority_id: AuthorityId,
 }
 
-/// Registration of an Ethereum address as a device.
-#[derive(Clone, Encode, Decode, PartialEq, Eq, Debug)]
+/// How a [`SignedMessage`] is hashed to be signed.
+#[derive(Clone, Copy, Default, Encode, Decode, PartialEq, Eq, Debug)]
 #[cfg_attr(
     feature = "runtime",
     derive(DecodeWithMemTracking, TypeInfo, MaxEncodedLen)
 )]
-pub struct EthRegistration<Cx> {
+pub enum MessageMode {
+    /// `personal_sign` over the binary payload of the message.
+    #[default]
+    PersonalSign,
+    /// EIP-712 typed data, so wallets show its fields:
+    ///
+    /// ```text
+    /// EIP712Domain(string name,uint256 chainId,bytes32 salt)
+    /// SignedMessage(bytes context,bytes32 challenge,bytes32 authority)
+    /// ```
+    ///
+    /// The domain is named `PalletPass`, bound to the chain told by the runtime's [`Config`],
+    /// and salted with the authority verifying the message.
+    TypedData,
+    /// `personal_sign` over an EIP-4361 (Sign-In with Ethereum) text message, which wallets
+    /// show as a login prompt:
+    ///
+    /// ```text
+    /// {authority} wants you to sign in with your Ethereum account:
+    /// {address}
+    ///
+    /// Sign in with your Ethereum account to use your Pass account.
+    ///
+    /// URI: pass://{authority}
+    /// Version: 1
+    /// Chain ID: {chain_id}
+    /// Nonce: {challenge}
+    /// Issued At: {issued_at}
+    /// Expiration Time: {expiration_time}
+    /// ```
+    ///
+    /// The chain and times are those the runtime's [`Config`] tells of the challenge context,
+    /// and the message is rejected once it expires.
+    Siwe,
+}
+
+impl MessageMode {
+    /// The chain the message is signed for as told by `P`, if the mode binds it to one.
+    pub fn chain_id<Cx, P: Config<Cx>>(&self) -> Option<u64> {
+        match self {
+            MessageMode::PersonalSign => None,
+            MessageMode::TypedData | MessageMode::Siwe => P::chain_id(),
+        }
+    }
+}
+
+/// Registration of an Ethereum address as a device, with messages verified for what the `P`
+/// [`Config`] tells.
+///
+/// `mode` is encoded last. Registrations encoded before it existed end with the signature, and
+/// decode as [`MessageMode::PersonalSign`] when nothing follows them.
+#[derive(Clone, Encode, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "runtime", derive(TypeInfo, MaxEncodedLen))]
+#[cfg_attr(feature = "runtime", scale_info(skip_type_params(P)))]
+pub struct EthRegistration<Cx, P = ()> {
     pub address: EthAddress,
     pub message: SignedMessage<Cx>,
     /// 65-byte secp256k1 signature (r[32] || s[32] || v[1])
     pub signature: [u8; 65],
+    pub mode: MessageMode,
+    #[codec(skip)]
+    pub(crate) _config: PhantomData<P>,
+}
+
+impl<Cx, P> EthRegistration<Cx, P> {
+    pub fn new(
+        address: EthAddress,
+        message: SignedMessage<Cx>,
+        mode: MessageMode,
+        signature: [u8; 65],
+    ) -> Self {
+        Self {
+            address,
+            message,
+            signature,
+            mode,
+            _config: PhantomData,
+        }
+    }
+}
+
+impl<Cx: Decode, P> Decode for EthRegistration<Cx, P> {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+        Ok(Self {
+            address: Decode::decode(input)?,
+            message: Decode::decode(input)?,
+            signature: Decode::decode(input)?,
+            mode: decode_trailing_mode(input)?,
+            _config: PhantomData,
+        })
+    }
 }
 
+#[cfg(feature = "runtime")]
+impl<Cx: DecodeWithMemTracking, P> DecodeWithMemTracking for EthRegistration<Cx, P> {}
+
 /// A credential proving the user controls an Ethereum address.
+///
+/// Like in [`EthRegistration`], `mode` is encoded last, and credentials encoded before it
+/// existed decode as [`MessageMode::PersonalSign`] when nothing follows them.
+#[derive(Clone, Encode, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "runtime", derive(TypeInfo, MaxEncodedLen))]
+#[cfg_attr(feature = "runtime", scale_info(skip_type_params(P)))]
+pub struct EthSignature<Cx, P = ()> {
+    pub user_id: HashedUserId,
+    pub message: SignedMessage<Cx>,
+    /// 65-byte secp256k1 signature (r[32] || s[32] || v[1])
+    pub signature: [u8; 65],
+    pub mode: MessageMode,
+    #[codec(skip)]
+    pub(crate) _config: PhantomData<P>,
+}
+
+impl<Cx, P> EthSignature<Cx, P> {
+    pub fn new(
+        user_id: HashedUserId,
+        message: SignedMessage<Cx>,
+        mode: MessageMode,
+        signature: [u8; 65],
+    ) -> Self {
+        Self {
+            user_id,
+            message,
+            signature,
+            mode,
+            _config: PhantomData,
+        }
+    }
+}
+
+impl<Cx: Decode, P> Decode for EthSignature<Cx, P> {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+        Ok(Self {
+            user_id: Decode::decode(input)?,
+            message: Decode::decode(input)?,
+            signature: Decode::decode(input)?,
+            mode: decode_trailing_mode(input)?,
+            _config: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "runtime")]
+impl<Cx: DecodeWithMemTracking, P> DecodeWithMemTracking for EthSignature<Cx, P> {}
+
+/// The mode ending an [`EthRegistration`] or [`EthSignature`], which those encoded before it
+/// existed lack.
+fn decode_trailing_mode<I: Input>(input: &mut I) -> Result<MessageMode, codec::Error> {
+    match input.remaining_len()? {
+        Some(0) => Ok(MessageMode::PersonalSign),
+        _ => Decode::decode(input),
+    }
+}
+
+/// The longest signature accepted from smart-contract wallets, wrappers included.
+pub const MAX_CONTRACT_SIGNATURE_LEN: u32 = 2048;
+
+/// The signature of a smart-contract wallet, in whatever format the wallet understands.
+#[cfg(feature = "runtime")]
+pub type ContractSignatureBytes = BoundedVec<u8, ConstU32<MAX_CONTRACT_SIGNATURE_LEN>>;
+/// The signature of a smart-contract wallet, in whatever format the wallet understands.
+#[cfg(not(feature = "runtime"))]
+pub type ContractSignatureBytes = alloc::vec::Vec<u8>;
+
+/// Registration of an Ethereum address that may be a smart-contract wallet as a device.
+///
+/// Signatures are checked by the wallet contract as known to the `O` oracle or, where it knows
+/// no contract, taken for the signature of an EOA as in [`EthRegistration`], and messages are
+/// verified for what the `P` [`Config`] tells.
 #[derive(Clone, Encode, Decode, PartialEq, Eq, Debug)]
 #[cfg_attr(
     feature = "runtime",
     derive(DecodeWithMemTracking, TypeInfo, MaxEncodedLen)
 )]
-pub struct EthSignature<Cx> {
+#[cfg_attr(feature = "runtime", scale_info(skip_type_params(O, P)))]
+pub struct ContractRegistration<Cx, O = (), P = ()> {
+    pub address: EthAddress,
+    pub message: SignedMessage<Cx>,
+    pub mode: MessageMode,
+    /// Signature accepted by the wallet (EIP-1271), possibly wrapped as per EIP-6492
+    pub signature: ContractSignatureBytes,
+    #[codec(skip)]
+    pub(crate) _oracle: PhantomData<O>,
+    #[codec(skip)]
+    pub(crate) _config: PhantomData<P>,
+}
+
+impl<Cx, O, P> ContractRegistration<Cx, O, P> {
+    pub fn new(
+        address: EthAddress,
+        message: SignedMessage<Cx>,
+        mode: MessageMode,
+        signature: ContractSignatureBytes,
+    ) -> Self {
+        Self {
+            address,
+            message,
+            mode,
+            signature,
+            _oracle: PhantomData,
+            _config: PhantomData,
+        }
+    }
+}
+
+/// A credential proving the user controls an Ethereum address that may be a smart-contract
+/// wallet.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "runtime",
+    derive(DecodeWithMemTracking, TypeInfo, MaxEncodedLen)
+)]
+#[cfg_attr(feature = "runtime", scale_info(skip_type_params(O, P)))]
+pub struct ContractSignature<Cx, O = (), P = ()> {
     pub user_id: HashedUserId,
     pub message: SignedMessage<Cx>,
-    /// 65-byte secp256k1 signature (r[32] || s[32] || v[1])
-    pub signature: [u8; 65],
+    pub mode: MessageMode,
+    /// Signature accepted by the wallet (EIP-1271), possibly wrapped as per EIP-6492
+    pub signature: ContractSignatureBytes,
+    #[codec(skip)]
+    pub(crate) _oracle: PhantomData<O>,
+    #[codec(skip)]
+    pub(crate) _config: PhantomData<P>,
+}
+
+impl<Cx, O, P> ContractSignature<Cx, O, P> {
+    pub fn new(
+        user_id: HashedUserId,
+        message: SignedMessage<Cx>,
+        mode: MessageMode,
+        signature: ContractSignatureBytes,
+    ) -> Self {
+        Self {
+            user_id,
+            message,
+            mode,
+            signature,
+            _oracle: PhantomData,
+            _config: PhantomData,
+        }
+    }
 }
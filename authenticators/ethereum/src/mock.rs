@@ -1,9 +1,14 @@
+use crate::{Config, Eip1271Oracle, Eip6492Signature, EthAddress};
 use frame::{
-    deps::sp_runtime::MultiSignature,
+    deps::{
+        sp_core::{ecdsa, Pair},
+        sp_runtime::MultiSignature,
+    },
     testing_prelude::*,
     traits::{EqualPrivilegeOnly, Verify},
 };
 use traits_authn::{
+    composite_authenticator,
     util::AuthorityFromPalletId,
     {Challenger, ExtrinsicContext},
 };
@@ -89,13 +94,24 @@ impl Challenger for BlockChallenger {
     }
 }
 
+pub type Eoa = crate::Authenticator<BlockChallenger, AuthorityId, LocalChain>;
+pub type Wallet =
+    crate::ContractAuthenticator<BlockChallenger, AuthorityId, LocalChain, LocalChain>;
+
+composite_authenticator!(
+    pub Eth<AuthorityId> {
+        Eoa,
+        Wallet,
+    }
+);
+
 impl pallet_pass::Config for Test {
     type PalletsOrigin = OriginCaller;
     type WeightInfo = ();
     type RegisterOrigin = EnsureRootWithSuccess<Self::AccountId, RootAccount>;
     type AddressGenerator = ();
     type Balances = Balances;
-    type Authenticator = crate::Authenticator<BlockChallenger, AuthorityId>;
+    type Authenticator = EthAuthenticator;
     type Scheduler = Scheduler;
     type BlockNumberProvider = System;
     type RegistrarConsideration = ();
@@ -111,6 +127,79 @@ impl pallet_pass::Config for Test {
     type MaxFilteredAssets = ConstU32<5>;
 }
 
+parameter_types! {
+    pub WalletOwner: ecdsa::Pair = ecdsa::Pair::from_seed(&[2u8; 32]);
+    pub DeployedWallet: EthAddress = EthAddress::from_raw([0xCA; 20]);
+    pub CounterfactualWallet: EthAddress = EthAddress::from_raw([0xCF; 20]);
+}
+
+pub const WALLET_FACTORY: [u8; 20] = [0xFA; 20];
+pub const WALLET_DEPLOYMENT: &[u8] = b"deploy(owner)";
+
+/// A local stand-in for the chain messages are signed for and wallet contracts live in, with
+/// the ID of Ethereum mainnet and the clock of the mock runtime. It knows of a wallet at
+/// `DeployedWallet`, and of one `WALLET_FACTORY` would deploy at `CounterfactualWallet`, both
+/// owned by `WalletOwner` alone.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LocalChain;
+
+impl LocalChain {
+    fn is_signed_by_owner(hash: &[u8; 32], signature: &[u8]) -> bool {
+        signature
+            .try_into()
+            .ok()
+            .and_then(|signature| {
+                sp_io::crypto::secp256k1_ecdsa_recover_compressed(signature, hash).ok()
+            })
+            .is_some_and(|public| ecdsa::Public::from_raw(public) == WalletOwner::get().public())
+    }
+}
+
+impl Eip1271Oracle for LocalChain {
+    fn is_contract(address: &EthAddress) -> bool {
+        *address == DeployedWallet::get()
+    }
+
+    fn is_valid_signature(address: &EthAddress, hash: &[u8; 32], signature: &[u8]) -> bool {
+        Self::is_contract(address) && Self::is_signed_by_owner(hash, signature)
+    }
+
+    fn is_valid_counterfactual_signature(
+        address: &EthAddress,
+        hash: &[u8; 32],
+        counterfactual: &Eip6492Signature,
+    ) -> bool {
+        *address == CounterfactualWallet::get()
+            && counterfactual.factory == WALLET_FACTORY
+            && counterfactual.factory_calldata == WALLET_DEPLOYMENT
+            && Self::is_signed_by_owner(hash, counterfactual.signature)
+    }
+}
+
+/// When the genesis block of the mock runtime is made, with each block six seconds after the
+/// previous one.
+pub const GENESIS_TIME: u64 = 1_700_000_000;
+/// How long challenges are valid for: ten blocks.
+pub const CHALLENGE_LIFETIME: u64 = 60;
+
+impl Config<BlockNumberFor<Test>> for LocalChain {
+    fn chain_id() -> Option<u64> {
+        Some(1)
+    }
+
+    fn issued_at(block: &BlockNumberFor<Test>) -> Option<u64> {
+        GENESIS_TIME.checked_add(block.checked_mul(6)?)
+    }
+
+    fn expiration_time(block: &BlockNumberFor<Test>) -> Option<u64> {
+        Self::issued_at(block)?.checked_add(CHALLENGE_LIFETIME)
+    }
+
+    fn unix_time() -> Option<u64> {
+        Self::issued_at(&System::block_number())
+    }
+}
+
 pub fn new_test_ext() -> TestExternalities {
     let mut t = TestExternalities::default();
     t.execute_with(|| {
@@ -118,3 +207,12 @@ pub fn new_test_ext() -> TestExternalities {
     });
     t
 }
+
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::benchmarking::Config for Test {
+    type Context = BlockNumberFor<Test>;
+
+    fn worst_case_context() -> Self::Context {
+        u64::MAX
+    }
+}
@@ -1,41 +1,58 @@
 use super::*;
-use crate::eth::recover_eth_address;
 
-impl<Ch: Challenger, AuthId> From<EthRegistration<CxOf<Ch>>> for Device<Ch, AuthId> {
-    fn from(reg: EthRegistration<CxOf<Ch>>) -> Self {
+impl<Ch: Challenger, AuthId, P> From<EthRegistration<CxOf<Ch>, P>> for Device<Ch, AuthId, P> {
+    fn from(reg: EthRegistration<CxOf<Ch>, P>) -> Self {
         Self::new(reg.address)
     }
 }
 
-impl<Cx: Parameter + Encode + 'static> DeviceChallengeResponse<Cx> for EthRegistration<Cx> {
+impl<Cx: Parameter + Encode + 'static, P: crate::Config<Cx>> DeviceChallengeResponse<Cx>
+    for EthRegistration<Cx, P>
+{
     fn is_valid(&self) -> bool {
-        if !self.address.is_well_formed() {
-            log::debug!(target: LOG_TARGET, "Malformed EthAddress: non-zero padding bytes");
-            return false;
-        }
         log::debug!(
             target: LOG_TARGET,
-            "Verifying Ethereum registration of {:?} with signature",
+            "Verifying Ethereum registration of {} with signature",
             self.address,
         );
-        let hash = self.message.eth_message_hash();
-        match recover_eth_address(&hash, &self.signature) {
-            Some(recovered) => {
-                let valid = recovered == self.address;
-                if !valid {
-                    log::debug!(
-                        target: LOG_TARGET,
-                        "Address mismatch: recovered {:?}, expected {:?}",
-                        recovered, self.address,
-                    );
-                }
-                valid
-            }
-            None => {
-                log::debug!(target: LOG_TARGET, "Failed to recover Ethereum address");
-                false
-            }
-        }
+        self.verify()
+            .inspect_err(|e| log::debug!(target: LOG_TARGET, "Invalid registration: {e:?}"))
+            .is_ok()
+    }
+
+    fn used_challenge(&self) -> (Cx, Challenge) {
+        (self.message.context.clone(), self.message.challenge)
+    }
+
+    fn authority(&self) -> AuthorityId {
+        self.message.authority_id
+    }
+
+    fn device_id(&self) -> &DeviceId {
+        self.address.as_ref()
+    }
+}
+
+impl<Ch: Challenger, AuthId, O, P> From<ContractRegistration<CxOf<Ch>, O, P>>
+    for ContractDevice<Ch, AuthId, O, P>
+{
+    fn from(reg: ContractRegistration<CxOf<Ch>, O, P>) -> Self {
+        Self::new(reg.address)
+    }
+}
+
+impl<Cx: Parameter + Encode + 'static, O: Eip1271Oracle, P: crate::Config<Cx>>
+    DeviceChallengeResponse<Cx> for ContractRegistration<Cx, O, P>
+{
+    fn is_valid(&self) -> bool {
+        log::debug!(
+            target: LOG_TARGET,
+            "Verifying Ethereum registration of {} with a wallet signature",
+            self.address,
+        );
+        self.verify()
+            .inspect_err(|e| log::debug!(target: LOG_TARGET, "Invalid registration: {e:?}"))
+            .is_ok()
     }
 
     fn used_challenge(&self) -> (Cx, Challenge) {
@@ -1,8 +1,9 @@
 use super::*;
-use crate::eth::recover_eth_address;
 use traits_authn::UserChallengeResponse;
 
-impl<Cx: Parameter + Encode + 'static> UserChallengeResponse<Cx> for EthSignature<Cx> {
+impl<Cx: Parameter + Encode + 'static, P: crate::Config<Cx>> UserChallengeResponse<Cx>
+    for EthSignature<Cx, P>
+{
     fn is_valid(&self) -> bool {
         // Signature validation is deferred to the device's verify_credential.
         true
@@ -21,16 +22,53 @@ impl<Cx: Parameter + Encode + 'static> UserChallengeResponse<Cx> for EthSignatur
     }
 }
 
-impl<Cx: Encode> VerifyCredential<EthSignature<Cx>> for EthAddress {
-    fn verify(&mut self, credential: &EthSignature<Cx>) -> Option<()> {
-        self.is_well_formed().then_some(())?;
+impl<Cx: Encode, P: crate::Config<Cx>> VerifyCredential<EthSignature<Cx, P>> for EthAddress {
+    fn verify(&mut self, credential: &EthSignature<Cx, P>) -> Option<()> {
         log::debug!(
             target: LOG_TARGET,
-            "Verifying Ethereum signature for {:?}",
+            "Verifying Ethereum signature for {}",
             self,
         );
-        let hash = credential.message.eth_message_hash();
-        let recovered = recover_eth_address(&hash, &credential.signature)?;
-        (recovered == *self).then_some(())
+        credential
+            .verify(self)
+            .inspect_err(|e| log::debug!(target: LOG_TARGET, "Invalid signature: {e:?}"))
+            .ok()
+    }
+}
+
+impl<Cx: Parameter + Encode + 'static, O: Eip1271Oracle, P: crate::Config<Cx>>
+    UserChallengeResponse<Cx> for ContractSignature<Cx, O, P>
+{
+    fn is_valid(&self) -> bool {
+        // As with EOAs, the wallet is asked about the signature in verify_credential.
+        true
+    }
+
+    fn used_challenge(&self) -> (Cx, Challenge) {
+        (self.message.context.clone(), self.message.challenge)
+    }
+
+    fn authority(&self) -> AuthorityId {
+        self.message.authority_id
+    }
+
+    fn user_id(&self) -> HashedUserId {
+        self.user_id
+    }
+}
+
+impl<Cx: Encode, O: Eip1271Oracle, P: crate::Config<Cx>>
+    VerifyCredential<ContractSignature<Cx, O, P>> for EthAddress
+{
+    fn verify(&mut self, credential: &ContractSignature<Cx, O, P>) -> Option<()> {
+        log::debug!(
+            target: LOG_TARGET,
+            "Verifying wallet signature for {}",
+            self,
+        );
+        credential
+            .verify(self)
+            .inspect_err(|e| log::debug!(target: LOG_TARGET, "Invalid signature: {e:?}"))
+            .ok()
     }
 }
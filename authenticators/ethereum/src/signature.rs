@@ -0,0 +1,38 @@
+use super::*;
+
+/// The recovery id (0 or 1) encoded in `v`. Wallets use 0/1 or the legacy 27/28, while some
+/// hardware wallets encode the chain of the message as per EIP-155 (`chain_id * 2 + 35/36`),
+/// which is only accepted for `chain_id`.
+pub(crate) fn recovery_id(v: u8, chain_id: Option<u64>) -> Result<u8, EthError> {
+    match v {
+        0..=1 => Ok(v),
+        27..=28 => Ok(v - 27),
+        _ => chain_id
+            .and_then(|chain_id| chain_id.checked_mul(2)?.checked_add(35))
+            .and_then(|base| u64::from(v).checked_sub(base))
+            .filter(|id| *id <= 1)
+            .map(|id| id as u8)
+            .ok_or(EthError::BadRecoveryId),
+    }
+}
+
+/// Rewrite a `r[32] || s[32] || v[1]` signature of a message signed for `chain_id` (if any) in
+/// its canonical form, with a low `s` and `v` as 27 or 28.
+///
+/// Signatures verify with `v` as a recovery id (0 or 1), a legacy one (27 or 28), or an EIP-155
+/// one of the chain, so the same authorization can be submitted as up to three byte strings.
+/// Indexers and anything deduplicating signatures must key on this form instead. Signers may
+/// also use it to bring a high `s`, which doesn't verify, to its low form.
+pub fn normalize_signature(
+    signature: &[u8; 65],
+    chain_id: Option<u64>,
+) -> Result<[u8; 65], EthError> {
+    let recovery_id = recovery_id(signature[64], chain_id)?;
+    let mut rs: [u8; 64] = signature[..64].try_into().expect("64 bytes");
+    let recovery_id = secp256k1_recovery::to_low_s(&mut rs, recovery_id);
+
+    let mut normalized = [0u8; 65];
+    normalized[..64].copy_from_slice(&rs);
+    normalized[64] = 27 + recovery_id;
+    Ok(normalized)
+}
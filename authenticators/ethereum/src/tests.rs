@@ -1,5 +1,9 @@
 use crate::mock::*;
-use crate::{eth::recover_eth_address, EthAddress, EthRegistration, EthSignature, SignedMessage};
+use crate::{
+    eth::recover_eth_address, normalize_signature, Config, ContractRegistration, ContractSignature,
+    EthAddress, EthError, EthRegistration, EthSignature, MessageMode, ParseAddressError,
+    SignedMessage,
+};
 use frame::{
     deps::sp_core::{ecdsa, Pair},
     testing_prelude::*,
@@ -26,6 +30,15 @@ fn eth_address_of(pair: &ecdsa::Pair) -> EthAddress {
     EthAddress::from_raw(addr)
 }
 
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct OtherChain;
+
+impl Config<u64> for OtherChain {
+    fn chain_id() -> Option<u64> {
+        Some(2)
+    }
+}
+
 fn make_signature(xtc: &impl ExtrinsicContext) -> (SignedMessage<u64>, EthAddress, [u8; 65]) {
     let context = System::block_number();
     let message = SignedMessage {
@@ -55,11 +68,12 @@ mod registration {
                 Pass::register(
                     RuntimeOrigin::root(),
                     USER,
-                    EthRegistration {
+                    EthDeviceAttestation::Eoa(EthRegistration::new(
                         address,
                         message,
-                        signature,
-                    }
+                        MessageMode::PersonalSign,
+                        signature
+                    ))
                 ),
                 pallet_pass::Error::<Test>::DeviceAttestationInvalid,
             );
@@ -78,17 +92,54 @@ mod registration {
                 Pass::register(
                     RuntimeOrigin::root(),
                     USER,
-                    EthRegistration {
-                        address: wrong_address,
+                    EthDeviceAttestation::Eoa(EthRegistration::new(
+                        wrong_address,
                         message,
-                        signature,
-                    }
+                        MessageMode::PersonalSign,
+                        signature
+                    ))
                 ),
                 pallet_pass::Error::<Test>::DeviceAttestationInvalid,
             );
         })
     }
 
+    #[test]
+    fn registration_works_with_eip155_v_of_the_chain() {
+        new_test_ext().execute_with(|| {
+            let (message, address, mut signature) = make_signature(&UserAddress::get().encode());
+
+            // v = chain_id * 2 + 35 + recovery id, for chain 1
+            signature[64] = signature[64] - 27 + 37;
+
+            let registration = EthRegistration::<_, OtherChain>::new(
+                address,
+                message.clone(),
+                MessageMode::PersonalSign,
+                signature,
+            );
+            assert_eq!(registration.verify(), Err(EthError::BadRecoveryId));
+            let registration = EthRegistration::<_, ()>::new(
+                address,
+                message.clone(),
+                MessageMode::PersonalSign,
+                signature,
+            );
+            assert_eq!(registration.verify(), Err(EthError::BadRecoveryId));
+
+            assert_ok!(Pass::register(
+                RuntimeOrigin::root(),
+                USER,
+                EthDeviceAttestation::Eoa(EthRegistration::new(
+                    address,
+                    message,
+                    MessageMode::PersonalSign,
+                    signature
+                ))
+            ));
+        })
+    }
+
     #[test]
     fn registration_works_if_attestation_is_valid() {
         new_test_ext().execute_with(|| {
@@ -97,11 +148,12 @@ mod registration {
             assert_ok!(Pass::register(
                 RuntimeOrigin::root(),
                 USER,
-                EthRegistration {
+                EthDeviceAttestation::Eoa(EthRegistration::new(
                     address,
                     message,
-                    signature,
-                }
+                    MessageMode::PersonalSign,
+                    signature
+                ))
             ));
         })
     }
@@ -118,11 +170,12 @@ mod authentication {
             assert_ok!(Pass::register(
                 RuntimeOrigin::root(),
                 USER,
-                EthRegistration {
+                EthDeviceAttestation::Eoa(EthRegistration::new(
                     address,
                     message,
-                    signature,
-                }
+                    MessageMode::PersonalSign,
+                    signature
+                ))
             ));
         });
         t
@@ -135,11 +188,12 @@ mod authentication {
 
             let ext = pallet_pass::PassAuthenticate::<Test>::from(
                 address.as_ref().clone(),
-                EthSignature {
-                    user_id: USER,
+                EthCredential::Eoa(EthSignature::new(
+                    USER,
                     message,
+                    MessageMode::PersonalSign,
                     signature,
-                },
+                )),
             );
 
             let call: RuntimeCall = frame_system::Call::remark { remark: vec![] }.into();
@@ -171,11 +225,54 @@ mod authentication {
 
             let ext = pallet_pass::PassAuthenticate::<Test>::from(
                 address.as_ref().clone(),
-                EthSignature {
-                    user_id: USER,
+                EthCredential::Eoa(EthSignature::new(
+                    USER,
+                    message,
+                    MessageMode::PersonalSign,
+                    signature,
+                )),
+            );
+
+            assert_ok!(ext
+                .validate_only(
+                    None.into(),
+                    &call,
+                    &call.get_dispatch_info(),
+                    call.encoded_size(),
+                    TransactionSource::External,
+                    0
+                )
+                .map(|_| ()));
+        })
+    }
+
+    #[test]
+    fn authentication_works_with_eip155_v_of_the_chain() {
+        new_test_ext().execute_with(|| {
+            let extrinsic_version: u8 = 0;
+            let call: RuntimeCall = frame_system::Call::remark { remark: vec![] }.into();
+
+            let (message, address, mut signature) = make_signature(
+                &TxBaseImplication((extrinsic_version, call.clone())).using_encoded(blake2_256),
+            );
+            signature[64] = signature[64] - 27 + 37;
+
+            let credential = EthSignature::<_, OtherChain>::new(
+                USER,
+                message.clone(),
+                MessageMode::PersonalSign,
+                signature,
+            );
+            assert_eq!(credential.verify(&address), Err(EthError::BadRecoveryId));
+
+            let ext = pallet_pass::PassAuthenticate::<Test>::from(
+                address.as_ref().clone(),
+                EthCredential::Eoa(EthSignature::new(
+                    USER,
                     message,
+                    MessageMode::PersonalSign,
                     signature,
-                },
+                )),
             );
 
             assert_ok!(ext
@@ -203,7 +300,7 @@ mod eth_address {
             let hash = crate::eth::personal_sign_hash(message);
             let sig = pair.sign_prehashed(&hash);
 
-            let recovered = recover_eth_address(&hash, &sig.0).expect("should recover");
+            let recovered = recover_eth_address(&hash, &sig.0, None).expect("should recover");
             let expected = eth_address_of(&pair);
             assert_eq!(recovered, expected);
         })
@@ -220,7 +317,7 @@ mod eth_address {
             // Convert v from 0/1 to legacy 27/28
             sig[64] += 27;
 
-            let recovered = recover_eth_address(&hash, &sig).expect("should recover");
+            let recovered = recover_eth_address(&hash, &sig, None).expect("should recover");
             let expected = eth_address_of(&pair);
             assert_eq!(recovered, expected);
         })
@@ -234,7 +331,56 @@ mod eth_address {
             let mut sig = pair.sign_prehashed(&hash).0;
             sig[64] = 99; // Invalid v
 
-            assert!(recover_eth_address(&hash, &sig).is_none());
+            assert_eq!(
+                recover_eth_address(&hash, &sig, None),
+                Err(EthError::BadRecoveryId)
+            );
+        })
+    }
+
+    #[test]
+    fn recover_works_with_eip155_v_of_the_chain() {
+        new_test_ext().execute_with(|| {
+            let pair = EthKey::get();
+            let hash = crate::eth::personal_sign_hash(b"hello ethereum");
+            let mut sig = pair.sign_prehashed(&hash).0;
+
+            // v = chain_id * 2 + 35 + recovery id, for chain 1
+            sig[64] += 37;
+
+            assert_eq!(
+                recover_eth_address(&hash, &sig, Some(1)),
+                Ok(eth_address_of(&pair))
+            );
+            assert_eq!(
+                recover_eth_address(&hash, &sig, Some(2)),
+                Err(EthError::BadRecoveryId)
+            );
+            assert_eq!(
+                recover_eth_address(&hash, &sig, None),
+                Err(EthError::BadRecoveryId)
+            );
+        })
+    }
+
+    #[test]
+    fn recover_rejects_high_s() {
+        new_test_ext().execute_with(|| {
+            let pair = EthKey::get();
+            let hash = crate::eth::personal_sign_hash(b"hello ethereum");
+            let sig = pair.sign_prehashed(&hash).0;
+
+            // The same signature, with n - s and the opposite recovery id
+            let mut malleated = [0u8; 65];
+            malleated[..64].copy_from_slice(&secp256k1_recovery::complement_s(
+                sig[..64].try_into().unwrap(),
+            ));
+            malleated[64] = sig[64] ^ 1;
+
+            assert_eq!(
+                recover_eth_address(&hash, &malleated, None),
+                Err(EthError::HighS)
+            );
         })
     }
 
@@ -243,7 +389,41 @@ mod eth_address {
         new_test_ext().execute_with(|| {
             let hash = crate::eth::personal_sign_hash(b"test");
             let sig = [0u8; 65];
-            assert!(recover_eth_address(&hash, &sig).is_none());
+            assert_eq!(
+                recover_eth_address(&hash, &sig, None),
+                Err(EthError::RecoveryFailed)
+            );
+        })
+    }
+
+    #[test]
+    fn normalize_signature_gives_the_canonical_form() {
+        new_test_ext().execute_with(|| {
+            let pair = EthKey::get();
+            let hash = crate::eth::personal_sign_hash(b"hello ethereum");
+            let sig = pair.sign_prehashed(&hash).0;
+            let mut legacy = sig;
+            legacy[64] += 27;
+
+            assert_eq!(normalize_signature(&sig, None), Ok(legacy));
+            assert_eq!(normalize_signature(&legacy, None), Ok(legacy));
+
+            // v = chain_id * 2 + 35 + recovery id, for chain 1
+            let mut eip155 = sig;
+            eip155[64] += 37;
+            assert_eq!(normalize_signature(&eip155, Some(1)), Ok(legacy));
+            assert_eq!(
+                normalize_signature(&eip155, Some(2)),
+                Err(EthError::BadRecoveryId)
+            );
+
+            // The same signature, with n - s and the opposite recovery id
+            let mut malleated = [0u8; 65];
+            malleated[..64].copy_from_slice(&secp256k1_recovery::complement_s(
+                sig[..64].try_into().unwrap(),
+            ));
+            malleated[64] = sig[64] ^ 1;
+            assert_eq!(normalize_signature(&malleated, None), Ok(legacy));
         })
     }
 
@@ -260,18 +440,645 @@ mod eth_address {
             bytes[12..].copy_from_slice(&[0xAA; 20]);
             let malformed = EthAddress::decode(&mut &bytes[..]).unwrap();
 
+            let registration = EthRegistration::<_, LocalChain>::new(
+                malformed,
+                message.clone(),
+                MessageMode::PersonalSign,
+                signature,
+            );
+            assert_eq!(registration.verify(), Err(EthError::MalformedAddress));
+
+            assert_noop!(
+                Pass::register(
+                    RuntimeOrigin::root(),
+                    USER,
+                    EthDeviceAttestation::Eoa(EthRegistration::new(
+                        malformed,
+                        message,
+                        MessageMode::PersonalSign,
+                        signature
+                    ))
+                ),
+                pallet_pass::Error::<Test>::DeviceAttestationInvalid,
+            );
+        })
+    }
+}
+
+mod typed_data {
+    use super::*;
+
+    const MODE: MessageMode = MessageMode::TypedData;
+
+    #[test]
+    fn registration_and_authentication_work_with_typed_data() {
+        new_test_ext().execute_with(|| {
+            let (message, address, _) = make_signature(&UserAddress::get().encode());
+            let signature = message
+                .sign_with_mode::<LocalChain>(&EthKey::get(), MODE)
+                .expect("the message is valid");
+
+            assert_ok!(Pass::register(
+                RuntimeOrigin::root(),
+                USER,
+                EthDeviceAttestation::Eoa(EthRegistration::new(
+                    address,
+                    message.clone(),
+                    MODE,
+                    signature
+                ))
+            ));
+
+            let credential = EthSignature::<_, LocalChain>::new(USER, message, MODE, signature);
+            assert_eq!(credential.verify(&address), Ok(()));
+        })
+    }
+
+    #[test]
+    fn typed_data_signatures_only_verify_in_their_mode() {
+        new_test_ext().execute_with(|| {
+            let (message, address, _) = make_signature(&[]);
+            let signature = message
+                .sign_with_mode::<LocalChain>(&EthKey::get(), MODE)
+                .expect("the message is valid");
+            let mut registration =
+                EthRegistration::<_, LocalChain>::new(address, message.clone(), MODE, signature);
+            assert_eq!(registration.verify(), Ok(()));
+
+            registration.mode = MessageMode::PersonalSign;
+            assert_eq!(registration.verify(), Err(EthError::AddressMismatch));
+
+            // Verified for another chain
+            let registration =
+                EthRegistration::<_, OtherChain>::new(address, message.clone(), MODE, signature);
+            assert_eq!(registration.verify(), Err(EthError::AddressMismatch));
+
+            // Verified for no chain at all
+            let registration = EthRegistration::<_, ()>::new(address, message, MODE, signature);
+            assert_eq!(registration.verify(), Err(EthError::ModeNotSupported));
+        })
+    }
+
+    #[test]
+    fn mode_is_encoded_last_and_defaults_for_legacy_bytes() {
+        new_test_ext().execute_with(|| {
+            let (message, address, signature) = make_signature(&[]);
+
+            let registration = EthRegistration::<_>::new(address, message.clone(), MODE, signature);
+            let encoded = registration.encode();
+            assert_eq!(encoded.last(), Some(&MODE.encode()[0]));
+            assert_eq!(EthRegistration::decode(&mut &encoded[..]), Ok(registration));
+
+            // Encoded before messages had a mode
+            let legacy = (address, message.clone(), signature).encode();
+            assert_eq!(
+                EthRegistration::decode(&mut &legacy[..]),
+                Ok(EthRegistration::<_>::new(
+                    address,
+                    message.clone(),
+                    MessageMode::PersonalSign,
+                    signature
+                ))
+            );
+            let legacy = (USER, message.clone(), signature).encode();
+            assert_eq!(
+                EthSignature::decode(&mut &legacy[..]),
+                Ok(EthSignature::<_>::new(
+                    USER,
+                    message,
+                    MessageMode::PersonalSign,
+                    signature
+                ))
+            );
+        })
+    }
+
+    /// Signed by the key of the EIP-712 example (`keccak256("cow")`) over this typed data,
+    /// with a deterministic (RFC 6979) signature as `signTypedData` of ethers or viem makes:
+    ///
+    /// ```text
+    /// domain:  { name: "PalletPass", chainId: 1, salt: "pass.example" (zero-padded) }
+    /// message: { context: 0x0100000000000000, challenge: 0xabab…ab, authority: salt }
+    /// ```
+    #[test]
+    fn typed_data_matches_known_answer() {
+        new_test_ext().execute_with(|| {
+            let mut authority_id = [0u8; 32];
+            authority_id[..12].copy_from_slice(b"pass.example");
+            let message = SignedMessage {
+                context: 1u64,
+                challenge: [0xab; 32],
+                authority_id,
+            };
+            assert_eq!(
+                message.eip712_hash(1),
+                [
+                    0xd2, 0x18, 0xd4, 0xfe, 0x9b, 0x38, 0x6d, 0x00, 0x61, 0x24, 0xf2, 0xc7, 0xf7,
+                    0x04, 0xc0, 0x38, 0x6e, 0x1c, 0x1a, 0x06, 0x94, 0x5c, 0x15, 0x71, 0xbd, 0x1e,
+                    0xad, 0xe1, 0x91, 0xb2, 0xd4, 0x31,
+                ]
+            );
+
+            let signer = "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+                .parse()
+                .expect("valid address");
+            let signature = [
+                0xad, 0x6b, 0x48, 0x3e, 0xb4, 0x68, 0x06, 0x71, 0x14, 0x49, 0x2a, 0xfe, 0x43, 0x49,
+                0x76, 0xe7, 0x79, 0x9e, 0x9b, 0xfb, 0x3d, 0xd8, 0xc8, 0xc8, 0xd3, 0x0f, 0xb0, 0x36,
+                0x5b, 0x1d, 0x56, 0xee, 0x5a, 0x28, 0x9b, 0xbe, 0xad, 0x83, 0x7c, 0xa0, 0xaf, 0x9e,
+                0x29, 0x0d, 0x8a, 0xe4, 0xec, 0x19, 0xba, 0x14, 0xeb, 0xe0, 0x56, 0x7c, 0xd9, 0xcb,
+                0x7c, 0xf7, 0xbb, 0xdc, 0x53, 0xc0, 0x2f, 0xc1, 0x1c,
+            ];
+            let registration =
+                EthRegistration::<_, LocalChain>::new(signer, message, MODE, signature);
+            assert_eq!(registration.verify(), Ok(()));
+        })
+    }
+}
+
+mod siwe {
+    use super::*;
+
+    const MODE: MessageMode = MessageMode::Siwe;
+
+    /// The mock chain, with challenges that never expire.
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    struct NeverExpires;
+
+    impl Config<u64> for NeverExpires {
+        fn chain_id() -> Option<u64> {
+            <LocalChain as Config<u64>>::chain_id()
+        }
+
+        fn issued_at(block: &u64) -> Option<u64> {
+            <LocalChain as Config<u64>>::issued_at(block)
+        }
+    }
+
+    #[test]
+    fn siwe_message_follows_eip4361() {
+        let mut authority_id = [0u8; 32];
+        authority_id[..12].copy_from_slice(b"pass.example");
+        let message = SignedMessage {
+            context: 1u64,
+            challenge: [0xab; 32],
+            authority_id,
+        };
+        let signer = EthAddress::from_raw([
+            0x5a, 0xae, 0xb6, 0x05, 0x3f, 0x3e, 0x94, 0xc9, 0xb9, 0xa0, 0x9f, 0x33, 0x66, 0x94,
+            0x35, 0xe7, 0xef, 0x1b, 0xea, 0xed,
+        ]);
+
+        assert_eq!(
+            message.siwe_message(&signer, 1, 1_633_019_124, Some(1_633_019_424)),
+            format!(
+                "pass.example wants you to sign in with your Ethereum account:\n\
+                0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed\n\n\
+                Sign in with your Ethereum account to use your Pass account.\n\n\
+                URI: pass://pass.example\n\
+                Version: 1\n\
+                Chain ID: 1\n\
+                Nonce: {}\n\
+                Issued At: 2021-09-30T16:25:24Z\n\
+                Expiration Time: 2021-09-30T16:30:24Z",
+                "ab".repeat(32)
+            )
+        );
+
+        // Authorities that aren't host names are rendered as hex
+        let message = SignedMessage {
+            authority_id: [0xff; 32],
+            ..message
+        };
+        assert!(message
+            .siwe_message(&signer, 1, 0, None)
+            .starts_with(&format!("{} wants you", "ff".repeat(32))));
+    }
+
+    #[test]
+    fn registration_and_authentication_work_with_siwe() {
+        new_test_ext().execute_with(|| {
+            let (message, address, _) = make_signature(&UserAddress::get().encode());
+            let signature = message
+                .sign_with_mode::<LocalChain>(&EthKey::get(), MODE)
+                .expect("the message is valid");
+
+            assert_ok!(Pass::register(
+                RuntimeOrigin::root(),
+                USER,
+                EthDeviceAttestation::Eoa(EthRegistration::new(
+                    address,
+                    message.clone(),
+                    MODE,
+                    signature
+                ))
+            ));
+
+            let credential = EthSignature::<_, LocalChain>::new(USER, message, MODE, signature);
+            assert_eq!(credential.verify(&address), Ok(()));
+        })
+    }
+
+    #[test]
+    fn siwe_signatures_cover_the_whole_message() {
+        new_test_ext().execute_with(|| {
+            let (message, address, _) = make_signature(&[]);
+            let signature = message
+                .sign_with_mode::<LocalChain>(&EthKey::get(), MODE)
+                .expect("the message is valid");
+            let mut registration =
+                EthRegistration::<_, LocalChain>::new(address, message.clone(), MODE, signature);
+            assert_eq!(registration.verify(), Ok(()));
+
+            // Signed as text, not over the binary payload
+            registration.mode = MessageMode::PersonalSign;
+            assert_eq!(registration.verify(), Err(EthError::AddressMismatch));
+
+            // Verified without the expiration time it is signed with
+            let registration =
+                EthRegistration::<_, NeverExpires>::new(address, message.clone(), MODE, signature);
+            assert_eq!(registration.verify(), Err(EthError::AddressMismatch));
+
+            // Verified without the time it is issued at
+            let registration = EthRegistration::<_, ()>::new(address, message, MODE, signature);
+            assert_eq!(registration.verify(), Err(EthError::ModeNotSupported));
+        })
+    }
+
+    #[test]
+    fn expired_siwe_messages_are_rejected() {
+        new_test_ext().execute_with(|| {
+            let (message, address, _) = make_signature(&[]);
+            let signature = message
+                .sign_with_mode::<LocalChain>(&EthKey::get(), MODE)
+                .expect("the message is valid");
+            let registration =
+                EthRegistration::<_, LocalChain>::new(address, message, MODE, signature);
+
+            System::set_block_number(10);
+            assert_eq!(registration.verify(), Ok(()));
+
+            // Ten blocks after the challenge is issued
+            System::set_block_number(11);
+            assert_eq!(registration.verify(), Err(EthError::Expired));
+        })
+    }
+}
+
+mod errors {
+    use super::*;
+
+    #[test]
+    fn registration_reports_address_mismatch() {
+        new_test_ext().execute_with(|| {
+            let (message, address, signature) = make_signature(&[]);
+
+            let registration = EthRegistration::<_, LocalChain>::new(
+                address,
+                message,
+                MessageMode::PersonalSign,
+                signature,
+            );
+            assert_eq!(registration.verify(), Ok(()));
+
+            let registration = EthRegistration {
+                address: EthAddress::from_raw([0xAB; 20]),
+                ..registration
+            };
+            assert_eq!(registration.verify(), Err(EthError::AddressMismatch));
+        })
+    }
+
+    #[test]
+    fn signature_reports_failure_reason() {
+        new_test_ext().execute_with(|| {
+            let (message, address, signature) = make_signature(&[]);
+            let mut credential = EthSignature::<_, LocalChain>::new(
+                USER,
+                message,
+                MessageMode::PersonalSign,
+                signature,
+            );
+            assert_eq!(credential.verify(&address), Ok(()));
+
+            // Signed over a different challenge
+            credential.message.challenge = [0u8; 32];
+            assert_eq!(credential.verify(&address), Err(EthError::AddressMismatch));
+
+            credential.signature[64] = 29;
+            assert_eq!(credential.verify(&address), Err(EthError::BadRecoveryId));
+        })
+    }
+}
+
+mod contract_wallets {
+    use super::*;
+
+    fn wallet_signature(message: &SignedMessage<u64>) -> Vec<u8> {
+        message.sign(&WalletOwner::get()).to_vec()
+    }
+
+    /// `abi.encode(factory, calldata, signature) || magicSuffix`
+    fn wrap_eip6492(factory: [u8; 20], calldata: &[u8], signature: &[u8]) -> Vec<u8> {
+        let word = |n: usize| {
+            let mut word = [0u8; 32];
+            word[24..].copy_from_slice(&(n as u64).to_be_bytes());
+            word
+        };
+        let padded = |bytes: &[u8]| {
+            let mut padded = bytes.to_vec();
+            padded.resize(bytes.len().div_ceil(32) * 32, 0);
+            padded
+        };
+
+        let mut wrapped = [0u8; 12].to_vec();
+        wrapped.extend(factory);
+        wrapped.extend(word(96));
+        wrapped.extend(word(96 + 32 + padded(calldata).len()));
+        wrapped.extend(word(calldata.len()));
+        wrapped.extend(padded(calldata));
+        wrapped.extend(word(signature.len()));
+        wrapped.extend(padded(signature));
+        wrapped.extend([0x64, 0x92].repeat(16));
+        wrapped
+    }
+
+    #[test]
+    fn deployed_wallets_verify_through_the_oracle() {
+        new_test_ext().execute_with(|| {
+            let (message, _, _) = make_signature(&[]);
+            let signature = wallet_signature(&message);
+
+            let registration = ContractRegistration::<_, LocalChain>::new(
+                DeployedWallet::get(),
+                message.clone(),
+                MessageMode::PersonalSign,
+                signature.clone().try_into().unwrap(),
+            );
+            assert_eq!(registration.verify(), Ok(()));
+
+            let credential = ContractSignature::<_, LocalChain>::new(
+                USER,
+                message.clone(),
+                MessageMode::PersonalSign,
+                signature.clone().try_into().unwrap(),
+            );
+            assert_eq!(credential.verify(&DeployedWallet::get()), Ok(()));
+
+            // Someone other than the owner
+            let credential = ContractSignature::<_, LocalChain>::new(
+                USER,
+                message.clone(),
+                MessageMode::PersonalSign,
+                message.sign(&EthKey::get()).to_vec().try_into().unwrap(),
+            );
+            assert_eq!(
+                credential.verify(&DeployedWallet::get()),
+                Err(EthError::ContractSignatureRejected)
+            );
+
+            // An oracle that doesn't know the wallet
+            let registration = ContractRegistration::<_, ()>::new(
+                DeployedWallet::get(),
+                message,
+                MessageMode::PersonalSign,
+                signature.try_into().unwrap(),
+            );
+            assert_eq!(registration.verify(), Err(EthError::AddressMismatch));
+        })
+    }
+
+    #[test]
+    fn eoas_verify_without_the_oracle() {
+        new_test_ext().execute_with(|| {
+            let (message, address, signature) = make_signature(&[]);
+
+            let registration = ContractRegistration::<_, LocalChain>::new(
+                address,
+                message.clone(),
+                MessageMode::PersonalSign,
+                signature.to_vec().try_into().unwrap(),
+            );
+            assert_eq!(registration.verify(), Ok(()));
+
+            // Not a signature an EOA could make
+            let registration = ContractRegistration::<_, LocalChain>::new(
+                address,
+                message,
+                MessageMode::PersonalSign,
+                signature[..64].to_vec().try_into().unwrap(),
+            );
+            assert_eq!(
+                registration.verify(),
+                Err(EthError::ContractSignatureRejected)
+            );
+        })
+    }
+
+    #[test]
+    fn counterfactual_wallets_verify_through_eip6492() {
+        new_test_ext().execute_with(|| {
+            let (message, _, _) = make_signature(&[]);
+            let signature = wallet_signature(&message);
+            let registration = |signature: Vec<u8>| {
+                ContractRegistration::<_, LocalChain>::new(
+                    CounterfactualWallet::get(),
+                    message.clone(),
+                    MessageMode::PersonalSign,
+                    signature.try_into().unwrap(),
+                )
+            };
+
+            let wrapped = wrap_eip6492(WALLET_FACTORY, WALLET_DEPLOYMENT, &signature);
+            assert_eq!(registration(wrapped.clone()).verify(), Ok(()));
+
+            // Not wrapped, and no wallet is deployed there yet, so taken for an EOA
+            assert_eq!(
+                registration(signature.clone()).verify(),
+                Err(EthError::AddressMismatch)
+            );
+
+            let wrapped = wrap_eip6492([0xAB; 20], WALLET_DEPLOYMENT, &signature);
+            assert_eq!(
+                registration(wrapped).verify(),
+                Err(EthError::ContractSignatureRejected)
+            );
+
+            let mut wrapped = wrap_eip6492(WALLET_FACTORY, WALLET_DEPLOYMENT, &signature);
+            wrapped.drain(100..164);
+            assert_eq!(
+                registration(wrapped).verify(),
+                Err(EthError::MalformedEip6492Signature)
+            );
+        })
+    }
+
+    #[test]
+    fn wallets_register_and_authenticate_with_pass() {
+        new_test_ext().execute_with(|| {
+            let (message, _, _) = make_signature(&UserAddress::get().encode());
+
+            assert_ok!(Pass::register(
+                RuntimeOrigin::root(),
+                USER,
+                EthDeviceAttestation::Wallet(ContractRegistration::new(
+                    DeployedWallet::get(),
+                    message.clone(),
+                    MessageMode::PersonalSign,
+                    wallet_signature(&message).try_into().unwrap(),
+                ))
+            ));
+
+            let extrinsic_version: u8 = 0;
+            let call: RuntimeCall = frame_system::Call::remark { remark: vec![] }.into();
+            let (message, _, _) = make_signature(
+                &TxBaseImplication((extrinsic_version, call.clone())).using_encoded(blake2_256),
+            );
+            let authenticate = |signature: Vec<u8>| {
+                pallet_pass::PassAuthenticate::<Test>::from(
+                    DeployedWallet::get().as_ref().clone(),
+                    EthCredential::Wallet(ContractSignature::new(
+                        USER,
+                        message.clone(),
+                        MessageMode::PersonalSign,
+                        signature.try_into().unwrap(),
+                    )),
+                )
+                .validate_only(
+                    None.into(),
+                    &call,
+                    &call.get_dispatch_info(),
+                    call.encoded_size(),
+                    TransactionSource::External,
+                    0,
+                )
+                .map(|_| ())
+            };
+
+            assert_ok!(authenticate(wallet_signature(&message)));
+            // Signed by someone other than the owner, whose key the wallet isn't
+            assert_noop!(
+                authenticate(message.sign(&EthKey::get()).to_vec()),
+                InvalidTransaction::BadSigner
+            );
+        })
+    }
+
+    #[test]
+    fn wallets_reject_registrations_they_are_not_signed_for() {
+        new_test_ext().execute_with(|| {
+            let (message, _, signature) = make_signature(&UserAddress::get().encode());
+
             assert_noop!(
                 Pass::register(
                     RuntimeOrigin::root(),
                     USER,
-                    EthRegistration {
-                        address: malformed,
+                    EthDeviceAttestation::Wallet(ContractRegistration::new(
+                        DeployedWallet::get(),
                         message,
-                        signature,
-                    }
+                        MessageMode::PersonalSign,
+                        signature.to_vec().try_into().unwrap(),
+                    ))
                 ),
                 pallet_pass::Error::<Test>::DeviceAttestationInvalid,
             );
         })
     }
+
+    #[test]
+    fn counterfactual_wallets_register_with_pass() {
+        new_test_ext().execute_with(|| {
+            let (message, _, _) = make_signature(&UserAddress::get().encode());
+            let signature = wrap_eip6492(
+                WALLET_FACTORY,
+                WALLET_DEPLOYMENT,
+                &wallet_signature(&message),
+            );
+
+            assert_ok!(Pass::register(
+                RuntimeOrigin::root(),
+                USER,
+                EthDeviceAttestation::Wallet(ContractRegistration::new(
+                    CounterfactualWallet::get(),
+                    message,
+                    MessageMode::PersonalSign,
+                    signature.try_into().unwrap(),
+                ))
+            ));
+        })
+    }
+}
+
+mod eip55 {
+    use super::*;
+    use core::str::FromStr;
+
+    const CHECKSUMMED: [&str; 4] = [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ];
+
+    #[test]
+    fn addresses_display_and_parse_checksummed() {
+        for address in CHECKSUMMED {
+            let parsed = EthAddress::from_str(address).expect("valid address");
+            assert_eq!(parsed.to_string(), address);
+        }
+    }
+
+    #[test]
+    fn single_case_addresses_carry_no_checksum() {
+        for address in CHECKSUMMED {
+            let expected = EthAddress::from_str(address);
+            let (prefix, hex) = address.split_at(2);
+
+            assert_eq!(
+                EthAddress::from_str(&format!("{prefix}{}", hex.to_lowercase())),
+                expected
+            );
+            assert_eq!(
+                EthAddress::from_str(&format!("{prefix}{}", hex.to_uppercase())),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn parsing_reports_failure_reason() {
+        assert_eq!(
+            EthAddress::from_str("0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+            Err(ParseAddressError::BadChecksum)
+        );
+        assert_eq!(
+            EthAddress::from_str("5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+            Err(ParseAddressError::MissingPrefix)
+        );
+        assert_eq!(
+            EthAddress::from_str("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA"),
+            Err(ParseAddressError::BadLength)
+        );
+        assert_eq!(
+            EthAddress::from_str("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg"),
+            Err(ParseAddressError::InvalidHex)
+        );
+        assert_eq!(
+            ParseAddressError::BadChecksum.to_string(),
+            "invalid EIP-55 checksum"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_uses_the_checksummed_form() {
+        use serde::{de::value::StrDeserializer, Deserialize};
+
+        let address = CHECKSUMMED[0];
+        let deserializer = StrDeserializer::<serde::de::value::Error>::new(address);
+        assert_eq!(
+            EthAddress::deserialize(deserializer).map(|address| address.to_string()),
+            Ok(address.into())
+        );
+    }
 }
@@ -0,0 +1,37 @@
+//! Weights of the Ethereum authenticator, out of its `runtime-benchmarks` suite.
+
+use sp_runtime::Weight;
+
+/// Weight functions of the Ethereum authenticator, so composite authenticators can charge the
+/// cost of each of their variants.
+pub trait WeightInfo {
+    /// Verifying a [`EthRegistration`](crate::EthRegistration).
+    fn register() -> Weight;
+    /// Verifying a [`EthSignature`](crate::EthSignature) against its device.
+    fn authenticate() -> Weight;
+    /// Verifying a [`ContractRegistration`](crate::ContractRegistration) signed by an EOA,
+    /// besides asking the [`Eip1271Oracle`](crate::Eip1271Oracle) about it.
+    fn register_contract() -> Weight;
+    /// Verifying a [`ContractSignature`](crate::ContractSignature) made by an EOA against its
+    /// device, besides asking the [`Eip1271Oracle`](crate::Eip1271Oracle) about it.
+    fn authenticate_contract() -> Weight;
+}
+
+/// Estimates, until runtimes benchmark their own hardware.
+impl WeightInfo for () {
+    fn register() -> Weight {
+        Weight::from_parts(80_000_000, 0)
+    }
+
+    fn authenticate() -> Weight {
+        Weight::from_parts(80_000_000, 0)
+    }
+
+    fn register_contract() -> Weight {
+        Weight::from_parts(80_000_000, 0)
+    }
+
+    fn authenticate_contract() -> Weight {
+        Weight::from_parts(80_000_000, 0)
+    }
+}
@@ -0,0 +1,72 @@
+//! Benchmarks of the Nostr authenticator. BIP-340 signatures have no host function, so they're
+//! verified in the runtime itself.
+
+use super::*;
+use authenticator_benchmarking::worst_case_message;
+pub use authenticator_benchmarking::Config;
+use frame::benchmarking::prelude::*;
+use k256::schnorr::SigningKey;
+use traits_authn::{util::VerifyCredential, DeviceChallengeResponse};
+
+pub struct Pallet<T: Config>(frame_system::Pallet<T>);
+
+impl<Cx> authenticator_benchmarking::SignedMessage<Cx> for SignedMessage<Cx> {
+    fn new(context: Cx, challenge: Challenge, authority_id: AuthorityId) -> Self {
+        SignedMessage {
+            context,
+            challenge,
+            authority_id,
+        }
+    }
+}
+
+fn signing_key() -> (SigningKey, NostrPubkey) {
+    let signing_key = SigningKey::from_bytes(&[1; 32]).expect("valid key");
+    let pubkey = NostrPubkey(signing_key.verifying_key().to_bytes().into());
+    (signing_key, pubkey)
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn register() {
+        let (signing_key, pubkey) = signing_key();
+        let message = worst_case_message::<T, SignedMessage<_>>();
+        let registration = NostrRegistration {
+            pubkey,
+            signature: message.sign(&signing_key),
+            message,
+        };
+
+        let valid;
+        #[block]
+        {
+            valid = registration.is_valid();
+        }
+
+        assert!(valid);
+    }
+
+    #[benchmark]
+    fn authenticate() {
+        let (signing_key, mut pubkey) = signing_key();
+        let message = worst_case_message::<T, SignedMessage<_>>();
+        let credential = NostrSignature {
+            user_id: [0xff; 32],
+            signature: message.sign(&signing_key),
+            message,
+        };
+
+        let verified;
+        #[block]
+        {
+            verified = pubkey.verify(&credential);
+        }
+
+        assert!(verified.is_some());
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -19,6 +19,11 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(feature = "runtime")]
+pub mod weights;
+
 #[cfg(feature = "runtime")]
 mod runtime {
     use super::*;
@@ -35,6 +40,8 @@ mod runtime {
 
 #[cfg(feature = "runtime")]
 pub use runtime::{Authenticator, Device};
+#[cfg(feature = "runtime")]
+pub use weights::WeightInfo;
 
 #[cfg(feature = "runtime")]
 mod schnorr;
@@ -47,6 +54,17 @@ mod schnorr;
 )]
 pub struct NostrPubkey(pub [u8; 32]);
 
+/// Why a Nostr signature failed to verify.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NostrError {
+    /// The public key is not the x coordinate of a secp256k1 point.
+    InvalidPubkey,
+    /// The signature is not a well-formed BIP-340 signature.
+    MalformedSignature,
+    /// The signature doesn't verify against the public key.
+    InvalidSignature,
+}
+
 impl AsRef<DeviceId> for NostrPubkey {
     fn as_ref(&self) -> &DeviceId {
         &self.0
@@ -1,5 +1,4 @@
 use super::*;
-use crate::schnorr::verify_schnorr;
 
 impl<Ch: Challenger, AuthId> From<NostrRegistration<CxOf<Ch>>> for Device<Ch, AuthId> {
     fn from(reg: NostrRegistration<CxOf<Ch>>) -> Self {
@@ -14,8 +13,9 @@ impl<Cx: Parameter + Encode + 'static> DeviceChallengeResponse<Cx> for NostrRegi
             "Verifying Nostr registration of {:?}",
             self.pubkey,
         );
-        let hash = self.message.message_hash();
-        verify_schnorr(&self.pubkey, &hash, &self.signature)
+        self.verify()
+            .inspect_err(|e| log::debug!(target: LOG_TARGET, "Invalid registration: {e:?}"))
+            .is_ok()
     }
 
     fn used_challenge(&self) -> (Cx, Challenge) {
@@ -1,5 +1,4 @@
 use super::*;
-use crate::schnorr::verify_schnorr;
 use traits_authn::UserChallengeResponse;
 
 impl<Cx: Parameter + Encode + 'static> UserChallengeResponse<Cx> for NostrSignature<Cx> {
@@ -27,7 +26,9 @@ impl<Cx: Encode> VerifyCredential<NostrSignature<Cx>> for NostrPubkey {
             "Verifying Nostr signature for {:?}",
             self,
         );
-        let hash = credential.message.message_hash();
-        verify_schnorr(self, &hash, &credential.signature).then_some(())
+        credential
+            .verify(self)
+            .inspect_err(|e| log::debug!(target: LOG_TARGET, "Invalid signature: {e:?}"))
+            .ok()
     }
 }
@@ -40,14 +40,31 @@ fn bip340_tagged_hash(tag: &[u8], msg: &[u8]) -> [u8; 32] {
 }
 
 /// Verify a BIP-340 Schnorr signature against a Nostr x-only public key.
-pub fn verify_schnorr(pubkey: &NostrPubkey, message_hash: &[u8; 32], signature: &[u8; 64]) -> bool {
-    let Ok(vk) = k256::schnorr::VerifyingKey::from_bytes(&pubkey.0) else {
-        return false;
-    };
-    let Ok(sig) = k256::schnorr::Signature::try_from(signature.as_slice()) else {
-        return false;
-    };
-    vk.verify_prehash(message_hash, &sig).is_ok()
+pub fn verify_schnorr(
+    pubkey: &NostrPubkey,
+    message_hash: &[u8; 32],
+    signature: &[u8; 64],
+) -> Result<(), NostrError> {
+    let vk = k256::schnorr::VerifyingKey::from_bytes(&pubkey.0)
+        .map_err(|_| NostrError::InvalidPubkey)?;
+    let sig = k256::schnorr::Signature::try_from(signature.as_slice())
+        .map_err(|_| NostrError::MalformedSignature)?;
+    vk.verify_prehash(message_hash, &sig)
+        .map_err(|_| NostrError::InvalidSignature)
+}
+
+impl<Cx: Encode> NostrRegistration<Cx> {
+    /// Verify that the registration is signed by the key being registered.
+    pub fn verify(&self) -> Result<(), NostrError> {
+        verify_schnorr(&self.pubkey, &self.message.message_hash(), &self.signature)
+    }
+}
+
+impl<Cx: Encode> NostrSignature<Cx> {
+    /// Verify that the credential is signed by `pubkey`.
+    pub fn verify(&self, pubkey: &NostrPubkey) -> Result<(), NostrError> {
+        verify_schnorr(pubkey, &self.message.message_hash(), &self.signature)
+    }
 }
 
 #[cfg(feature = "full-crypto")]
@@ -1,5 +1,5 @@
 use crate::mock::*;
-use crate::{NostrPubkey, NostrRegistration, NostrSignature, SignedMessage};
+use crate::{NostrError, NostrPubkey, NostrRegistration, NostrSignature, SignedMessage};
 use frame::testing_prelude::*;
 use frame::traits::TxBaseImplication;
 use traits_authn::{Challenger, ExtrinsicContext, HashedUserId};
@@ -191,9 +191,10 @@ mod schnorr_verification {
             let sig: k256::schnorr::Signature = sk.sign_prehash(&msg_hash).expect("sign ok");
             let sig_bytes: [u8; 64] = sig.to_bytes().into();
 
-            assert!(crate::schnorr::verify_schnorr(
-                &pubkey, &msg_hash, &sig_bytes
-            ));
+            assert_eq!(
+                crate::schnorr::verify_schnorr(&pubkey, &msg_hash, &sig_bytes),
+                Ok(())
+            );
         })
     }
 
@@ -209,11 +210,10 @@ mod schnorr_verification {
             let sig: k256::schnorr::Signature = sk.sign_prehash(&msg_hash).expect("sign ok");
             let sig_bytes: [u8; 64] = sig.to_bytes().into();
 
-            assert!(!crate::schnorr::verify_schnorr(
-                &wrong_pubkey,
-                &msg_hash,
-                &sig_bytes
-            ));
+            assert_eq!(
+                crate::schnorr::verify_schnorr(&wrong_pubkey, &msg_hash, &sig_bytes),
+                Err(NostrError::InvalidSignature)
+            );
         })
     }
 
@@ -224,11 +224,10 @@ mod schnorr_verification {
             let bad_pubkey = NostrPubkey([0u8; 32]);
             let msg_hash = [0u8; 32];
             let sig = [0u8; 64];
-            assert!(!crate::schnorr::verify_schnorr(
-                &bad_pubkey,
-                &msg_hash,
-                &sig
-            ));
+            assert_eq!(
+                crate::schnorr::verify_schnorr(&bad_pubkey, &msg_hash, &sig),
+                Err(NostrError::InvalidPubkey)
+            );
         })
     }
 
@@ -238,7 +237,10 @@ mod schnorr_verification {
             let (_sk, pubkey) = nostr_keypair();
             let msg_hash = sp_io::hashing::sha2_256(b"test");
             let sig = [0u8; 64];
-            assert!(!crate::schnorr::verify_schnorr(&pubkey, &msg_hash, &sig));
+            assert_eq!(
+                crate::schnorr::verify_schnorr(&pubkey, &msg_hash, &sig),
+                Err(NostrError::MalformedSignature)
+            );
         })
     }
 
@@ -252,9 +254,10 @@ mod schnorr_verification {
             let mut sig_bytes: [u8; 64] = sig.to_bytes().into();
             sig_bytes[0] ^= 0x01; // Flip one bit
 
-            assert!(!crate::schnorr::verify_schnorr(
-                &pubkey, &msg_hash, &sig_bytes
-            ));
+            assert_eq!(
+                crate::schnorr::verify_schnorr(&pubkey, &msg_hash, &sig_bytes),
+                Err(NostrError::InvalidSignature)
+            );
         })
     }
 }
@@ -0,0 +1,109 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Recovers public keys from secp256k1 signatures, accepting a single form of each signature.
+//!
+//! ECDSA signatures are malleable: for every valid `(r, s)`, `(r, n - s)` verifies the same
+//! message under the opposite recovery id, so anyone could submit an authorization as two
+//! different byte strings. Like Ethereum (EIP-2) and Bitcoin (BIP-62), signatures whose `s` is
+//! in the upper half of the curve order are rejected. Signers producing them can normalize
+//! them with [`to_low_s`] beforehand.
+//!
+//! Signatures are `r[32] || s[32]`, with their recovery id (0 to 3) passed separately, as each
+//! chain encodes it its own way.
+//!
+//! Recovery takes the host functions of `sp-io`, behind the `recover` feature. Signers only
+//! normalizing their signatures can go without it.
+
+#[cfg(test)]
+mod tests;
+
+/// The order `n` of the secp256k1 curve.
+const ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// `n / 2`, the highest `s` accepted.
+pub const HALF_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+/// Why no public key was recovered from a signature.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// `s` is above [`HALF_ORDER`].
+    HighS,
+    /// The recovery id is not within 0 and 3.
+    BadRecoveryId,
+    /// No public key can be recovered from the signature.
+    RecoveryFailed,
+}
+
+/// Whether `s` of `signature` is at most [`HALF_ORDER`].
+pub fn is_low_s(signature: &[u8; 64]) -> bool {
+    // Big-endian integers compare as their bytes
+    signature[32..] <= HALF_ORDER[..]
+}
+
+/// The other form of `signature`, `(r, n - s)`, which verifies under the opposite recovery id.
+pub fn complement_s(signature: &[u8; 64]) -> [u8; 64] {
+    let mut complement = *signature;
+    let mut borrow = 0;
+    for i in (0..32).rev() {
+        let (diff, under) = ORDER[i].overflowing_sub(signature[32 + i]);
+        let (diff, under_borrow) = diff.overflowing_sub(borrow);
+        complement[32 + i] = diff;
+        borrow = u8::from(under || under_borrow);
+    }
+    complement
+}
+
+/// Bring `signature` to its low-s form, returning its recovery id (which flips when `s` does).
+pub fn to_low_s(signature: &mut [u8; 64], recovery_id: u8) -> u8 {
+    if is_low_s(signature) {
+        return recovery_id;
+    }
+    *signature = complement_s(signature);
+    recovery_id ^ 1
+}
+
+#[cfg(feature = "recover")]
+fn checked(signature: &[u8; 64], recovery_id: u8) -> Result<[u8; 65], Error> {
+    if recovery_id > 3 {
+        return Err(Error::BadRecoveryId);
+    }
+    if !is_low_s(signature) {
+        return Err(Error::HighS);
+    }
+    let mut sig = [0u8; 65];
+    sig[..64].copy_from_slice(signature);
+    sig[64] = recovery_id;
+    Ok(sig)
+}
+
+/// Recover the uncompressed public key (64 bytes, without the `0x04` prefix) that signed
+/// `message_hash`.
+#[cfg(feature = "recover")]
+pub fn recover(
+    signature: &[u8; 64],
+    recovery_id: u8,
+    message_hash: &[u8; 32],
+) -> Result<[u8; 64], Error> {
+    sp_io::crypto::secp256k1_ecdsa_recover(&checked(signature, recovery_id)?, message_hash)
+        .map_err(|_| Error::RecoveryFailed)
+}
+
+/// Recover the compressed public key (33 bytes) that signed `message_hash`.
+#[cfg(feature = "recover")]
+pub fn recover_compressed(
+    signature: &[u8; 64],
+    recovery_id: u8,
+    message_hash: &[u8; 32],
+) -> Result<[u8; 33], Error> {
+    sp_io::crypto::secp256k1_ecdsa_recover_compressed(
+        &checked(signature, recovery_id)?,
+        message_hash,
+    )
+    .map_err(|_| Error::RecoveryFailed)
+}
@@ -0,0 +1,70 @@
+use super::*;
+use sp_core::{ecdsa, Pair};
+
+const MESSAGE_HASH: [u8; 32] = [0x42; 32];
+
+fn sign(seed: u8) -> (ecdsa::Pair, [u8; 64], u8) {
+    let pair = ecdsa::Pair::from_seed(&[seed; 32]);
+    let signature = pair.sign_prehashed(&MESSAGE_HASH).0;
+    let (rs, recovery_id) = signature.split_at(64);
+    (pair, rs.try_into().unwrap(), recovery_id[0])
+}
+
+#[test]
+fn recovers_the_signer() {
+    let (pair, signature, recovery_id) = sign(1);
+    assert!(is_low_s(&signature));
+
+    assert_eq!(
+        recover_compressed(&signature, recovery_id, &MESSAGE_HASH),
+        Ok(pair.public().0)
+    );
+    assert!(recover(&signature, recovery_id, &MESSAGE_HASH).is_ok());
+}
+
+#[test]
+fn rejects_high_s() {
+    for seed in 1..=8 {
+        let (pair, signature, recovery_id) = sign(seed);
+        let high = complement_s(&signature);
+        assert!(!is_low_s(&high));
+
+        assert_eq!(
+            recover_compressed(&high, recovery_id ^ 1, &MESSAGE_HASH),
+            Err(Error::HighS)
+        );
+        assert_eq!(
+            recover(&high, recovery_id ^ 1, &MESSAGE_HASH),
+            Err(Error::HighS)
+        );
+
+        // Normalized back into the signature it was made of
+        let mut normalized = high;
+        assert_eq!(to_low_s(&mut normalized, recovery_id ^ 1), recovery_id);
+        assert_eq!(normalized, signature);
+        assert_eq!(
+            recover_compressed(&normalized, recovery_id, &MESSAGE_HASH),
+            Ok(pair.public().0)
+        );
+    }
+}
+
+#[test]
+fn half_order_is_the_highest_s() {
+    let mut signature = [0x01; 64];
+    signature[32..].copy_from_slice(&HALF_ORDER);
+    assert!(is_low_s(&signature));
+
+    signature[63] += 1;
+    assert!(!is_low_s(&signature));
+    assert_eq!(complement_s(&signature)[32..], HALF_ORDER);
+}
+
+#[test]
+fn rejects_bad_recovery_ids() {
+    let (_, signature, _) = sign(1);
+    assert_eq!(
+        recover(&signature, 4, &MESSAGE_HASH),
+        Err(Error::BadRecoveryId)
+    );
+}
@@ -19,6 +19,11 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(feature = "runtime")]
+pub mod weights;
+
 #[cfg(feature = "runtime")]
 mod runtime {
     use super::*;
@@ -35,6 +40,8 @@ mod runtime {
 
 #[cfg(feature = "runtime")]
 pub use runtime::{Authenticator, Device};
+#[cfg(feature = "runtime")]
+pub use weights::WeightInfo;
 
 #[cfg(feature = "runtime")]
 mod sol;
@@ -47,6 +54,13 @@ mod sol;
 )]
 pub struct SolPubkey(pub [u8; 32]);
 
+/// Why a Solana signature failed to verify.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SolError {
+    /// The signature doesn't verify against the public key.
+    InvalidSignature,
+}
+
 impl AsRef<DeviceId> for SolPubkey {
     fn as_ref(&self) -> &DeviceId {
         &self.0
@@ -1,5 +1,4 @@
 use super::*;
-use crate::sol::verify_ed25519;
 
 impl<Ch: Challenger, AuthId> From<SolRegistration<CxOf<Ch>>> for Device<Ch, AuthId> {
     fn from(reg: SolRegistration<CxOf<Ch>>) -> Self {
@@ -14,8 +13,9 @@ impl<Cx: Parameter + Encode + 'static> DeviceChallengeResponse<Cx> for SolRegist
             "Verifying Solana registration of {:?}",
             self.pubkey,
         );
-        let payload = self.message.payload();
-        verify_ed25519(&self.pubkey, &payload, &self.signature)
+        self.verify()
+            .inspect_err(|e| log::debug!(target: LOG_TARGET, "Invalid registration: {e:?}"))
+            .is_ok()
     }
 
     fn used_challenge(&self) -> (Cx, Challenge) {
@@ -1,5 +1,4 @@
 use super::*;
-use crate::sol::verify_ed25519;
 use traits_authn::UserChallengeResponse;
 
 impl<Cx: Parameter + Encode + 'static> UserChallengeResponse<Cx> for SolSignature<Cx> {
@@ -27,7 +26,9 @@ impl<Cx: Encode> VerifyCredential<SolSignature<Cx>> for SolPubkey {
             "Verifying Solana signature for {:?}",
             self,
         );
-        let payload = credential.message.payload();
-        verify_ed25519(self, &payload, &credential.signature).then_some(())
+        credential
+            .verify(self)
+            .inspect_err(|e| log::debug!(target: LOG_TARGET, "Invalid signature: {e:?}"))
+            .ok()
     }
 }
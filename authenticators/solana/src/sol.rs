@@ -18,11 +18,31 @@ impl<Cx: Encode> SignedMessage<Cx> {
 }
 
 /// Verify an Ed25519 signature against a Solana public key.
-pub fn verify_ed25519(pubkey: &SolPubkey, message: &[u8], signature: &[u8; 64]) -> bool {
+pub fn verify_ed25519(
+    pubkey: &SolPubkey,
+    message: &[u8],
+    signature: &[u8; 64],
+) -> Result<(), SolError> {
     // Use sp_io's ed25519 verification
     let ed_pub = sp_core::ed25519::Public::from_raw(pubkey.0);
     let ed_sig = sp_core::ed25519::Signature::from_raw(*signature);
     sp_io::crypto::ed25519_verify(&ed_sig, message, &ed_pub)
+        .then_some(())
+        .ok_or(SolError::InvalidSignature)
+}
+
+impl<Cx: Encode> SolRegistration<Cx> {
+    /// Verify that the registration is signed by the key being registered.
+    pub fn verify(&self) -> Result<(), SolError> {
+        verify_ed25519(&self.pubkey, &self.message.payload(), &self.signature)
+    }
+}
+
+impl<Cx: Encode> SolSignature<Cx> {
+    /// Verify that the credential is signed by `pubkey`.
+    pub fn verify(&self, pubkey: &SolPubkey) -> Result<(), SolError> {
+        verify_ed25519(pubkey, &self.message.payload(), &self.signature)
+    }
 }
 
 #[cfg(feature = "full-crypto")]
@@ -1,5 +1,5 @@
 use crate::mock::*;
-use crate::{SignedMessage, SolPubkey, SolRegistration, SolSignature};
+use crate::{SignedMessage, SolError, SolPubkey, SolRegistration, SolSignature};
 use frame::{
     deps::sp_core::{ed25519, Pair},
     testing_prelude::*,
@@ -190,7 +190,10 @@ mod edge_cases {
             let sig = pair.sign(payload);
 
             let zero_pubkey = SolPubkey([0u8; 32]);
-            assert!(!crate::sol::verify_ed25519(&zero_pubkey, payload, &sig.0));
+            assert_eq!(
+                crate::sol::verify_ed25519(&zero_pubkey, payload, &sig.0),
+                Err(SolError::InvalidSignature)
+            );
         })
     }
 
@@ -200,7 +203,10 @@ mod edge_cases {
             let pair = SolKey::get();
             let pubkey = sol_pubkey_of(&pair);
             let sig = [0u8; 64];
-            assert!(!crate::sol::verify_ed25519(&pubkey, b"test", &sig));
+            assert_eq!(
+                crate::sol::verify_ed25519(&pubkey, b"test", &sig),
+                Err(SolError::InvalidSignature)
+            );
         })
     }
 
@@ -213,7 +219,10 @@ mod edge_cases {
             let mut sig = pair.sign(payload).0;
             sig[0] ^= 0x01;
 
-            assert!(!crate::sol::verify_ed25519(&pubkey, payload, &sig));
+            assert_eq!(
+                crate::sol::verify_ed25519(&pubkey, payload, &sig),
+                Err(SolError::InvalidSignature)
+            );
         })
     }
 }
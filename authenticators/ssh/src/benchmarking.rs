@@ -0,0 +1,67 @@
+//! Benchmarks of the SSH authenticator: building the SSHSIG signed data of messages with the
+//! longest challenge context, and verifying its Ed25519 signature.
+
+use super::*;
+use authenticator_benchmarking::worst_case_message;
+pub use authenticator_benchmarking::Config;
+use frame::benchmarking::prelude::*;
+use sp_core::{ed25519, Pair};
+use traits_authn::{util::VerifyCredential, DeviceChallengeResponse};
+
+pub struct Pallet<T: Config>(frame_system::Pallet<T>);
+
+impl<Cx> authenticator_benchmarking::SignedMessage<Cx> for SignedMessage<Cx> {
+    fn new(context: Cx, challenge: Challenge, authority_id: AuthorityId) -> Self {
+        SignedMessage {
+            context,
+            challenge,
+            authority_id,
+        }
+    }
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn register() {
+        let pair = ed25519::Pair::from_seed(&[1; 32]);
+        let message = worst_case_message::<T, SignedMessage<_>>();
+        let registration = SshRegistration {
+            pubkey: SshPubkey(pair.public().0),
+            signature: message.sign(&pair),
+            message,
+        };
+
+        let valid;
+        #[block]
+        {
+            valid = registration.is_valid();
+        }
+
+        assert!(valid);
+    }
+
+    #[benchmark]
+    fn authenticate() {
+        let pair = ed25519::Pair::from_seed(&[1; 32]);
+        let message = worst_case_message::<T, SignedMessage<_>>();
+        let credential = SshSignature {
+            user_id: [0xff; 32],
+            signature: message.sign(&pair),
+            message,
+        };
+        let mut pubkey = SshPubkey(pair.public().0);
+
+        let verified;
+        #[block]
+        {
+            verified = pubkey.verify(&credential);
+        }
+
+        assert!(verified.is_some());
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
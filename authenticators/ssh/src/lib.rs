@@ -22,6 +22,11 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(feature = "runtime")]
+pub mod weights;
+
 #[cfg(feature = "runtime")]
 mod runtime {
     use super::*;
@@ -38,6 +43,8 @@ mod runtime {
 
 #[cfg(feature = "runtime")]
 pub use runtime::{Authenticator, Device};
+#[cfg(feature = "runtime")]
+pub use weights::WeightInfo;
 
 #[cfg(feature = "runtime")]
 mod ssh;
@@ -50,6 +57,13 @@ mod ssh;
 )]
 pub struct SshPubkey(pub [u8; 32]);
 
+/// Why an SSH signature failed to verify.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SshError {
+    /// The signature doesn't verify against the public key.
+    InvalidSignature,
+}
+
 impl AsRef<DeviceId> for SshPubkey {
     fn as_ref(&self) -> &DeviceId {
         &self.0
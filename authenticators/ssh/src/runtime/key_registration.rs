@@ -1,5 +1,4 @@
 use super::*;
-use crate::ssh::verify_ssh_ed25519;
 
 impl<Ch: Challenger, AuthId> From<SshRegistration<CxOf<Ch>>> for Device<Ch, AuthId> {
     fn from(reg: SshRegistration<CxOf<Ch>>) -> Self {
@@ -14,8 +13,9 @@ impl<Cx: Parameter + Encode + 'static> DeviceChallengeResponse<Cx> for SshRegist
             "Verifying SSH Ed25519 registration of {:?}",
             self.pubkey,
         );
-        let signed_data = self.message.ssh_signed_data();
-        verify_ssh_ed25519(&self.pubkey, &signed_data, &self.signature)
+        self.verify()
+            .inspect_err(|e| log::debug!(target: LOG_TARGET, "Invalid registration: {e:?}"))
+            .is_ok()
     }
 
     fn used_challenge(&self) -> (Cx, Challenge) {
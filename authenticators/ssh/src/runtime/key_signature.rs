@@ -1,5 +1,4 @@
 use super::*;
-use crate::ssh::verify_ssh_ed25519;
 use traits_authn::UserChallengeResponse;
 
 impl<Cx: Parameter + Encode + 'static> UserChallengeResponse<Cx> for SshSignature<Cx> {
@@ -27,7 +26,9 @@ impl<Cx: Encode> VerifyCredential<SshSignature<Cx>> for SshPubkey {
             "Verifying SSH Ed25519 signature for {:?}",
             self,
         );
-        let signed_data = credential.message.ssh_signed_data();
-        verify_ssh_ed25519(self, &signed_data, &credential.signature).then_some(())
+        credential
+            .verify(self)
+            .inspect_err(|e| log::debug!(target: LOG_TARGET, "Invalid signature: {e:?}"))
+            .ok()
     }
 }
@@ -56,10 +56,34 @@ impl<Cx: Encode> SignedMessage<Cx> {
 }
 
 /// Verify an Ed25519 signature against an SSH public key using the SSHSIG format.
-pub fn verify_ssh_ed25519(pubkey: &SshPubkey, signed_data: &[u8], signature: &[u8; 64]) -> bool {
+pub fn verify_ssh_ed25519(
+    pubkey: &SshPubkey,
+    signed_data: &[u8],
+    signature: &[u8; 64],
+) -> Result<(), SshError> {
     let ed_pub = sp_core::ed25519::Public::from_raw(pubkey.0);
     let ed_sig = sp_core::ed25519::Signature::from_raw(*signature);
     sp_io::crypto::ed25519_verify(&ed_sig, signed_data, &ed_pub)
+        .then_some(())
+        .ok_or(SshError::InvalidSignature)
+}
+
+impl<Cx: Encode> SshRegistration<Cx> {
+    /// Verify that the registration is signed by the key being registered.
+    pub fn verify(&self) -> Result<(), SshError> {
+        verify_ssh_ed25519(
+            &self.pubkey,
+            &self.message.ssh_signed_data(),
+            &self.signature,
+        )
+    }
+}
+
+impl<Cx: Encode> SshSignature<Cx> {
+    /// Verify that the credential is signed by `pubkey`.
+    pub fn verify(&self, pubkey: &SshPubkey) -> Result<(), SshError> {
+        verify_ssh_ed25519(pubkey, &self.message.ssh_signed_data(), &self.signature)
+    }
 }
 
 #[cfg(feature = "full-crypto")]
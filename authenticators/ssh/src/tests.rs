@@ -1,5 +1,5 @@
 use crate::mock::*;
-use crate::{SignedMessage, SshPubkey, SshRegistration, SshSignature};
+use crate::{SignedMessage, SshError, SshPubkey, SshRegistration, SshSignature};
 use frame::{
     deps::sp_core::{ed25519, Pair},
     testing_prelude::*,
@@ -214,7 +214,10 @@ mod edge_cases {
             let sig = pair.sign(data);
 
             let zero_pubkey = SshPubkey([0u8; 32]);
-            assert!(!crate::ssh::verify_ssh_ed25519(&zero_pubkey, data, &sig.0));
+            assert_eq!(
+                crate::ssh::verify_ssh_ed25519(&zero_pubkey, data, &sig.0),
+                Err(SshError::InvalidSignature)
+            );
         })
     }
 
@@ -224,7 +227,10 @@ mod edge_cases {
             let pair = SshKey::get();
             let pubkey = ssh_pubkey_of(&pair);
             let sig = [0u8; 64];
-            assert!(!crate::ssh::verify_ssh_ed25519(&pubkey, b"test", &sig));
+            assert_eq!(
+                crate::ssh::verify_ssh_ed25519(&pubkey, b"test", &sig),
+                Err(SshError::InvalidSignature)
+            );
         })
     }
 
@@ -237,7 +243,10 @@ mod edge_cases {
             let mut sig = pair.sign(data.as_slice()).0;
             sig[0] ^= 0x01;
 
-            assert!(!crate::ssh::verify_ssh_ed25519(&pubkey, data, &sig));
+            assert_eq!(
+                crate::ssh::verify_ssh_ed25519(&pubkey, data, &sig),
+                Err(SshError::InvalidSignature)
+            );
         })
     }
 }
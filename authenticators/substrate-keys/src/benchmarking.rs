@@ -0,0 +1,73 @@
+//! Benchmarks of the Substrate keys authenticator, with ECDSA keys: unlike Ed25519 and Sr25519
+//! ones, their signatures are recovered, and hashed into an account to compare.
+
+use super::*;
+use authenticator_benchmarking::worst_case_message;
+pub use authenticator_benchmarking::Config;
+use frame::benchmarking::prelude::*;
+use sp_core::{ecdsa, Pair};
+use sp_runtime::{traits::IdentifyAccount, MultiSigner};
+use traits_authn::{util::VerifyCredential, DeviceChallengeResponse};
+
+pub struct Pallet<T: Config>(frame_system::Pallet<T>);
+
+impl<Cx> authenticator_benchmarking::SignedMessage<Cx> for SignedMessage<Cx> {
+    fn new(context: Cx, challenge: Challenge, authority_id: AuthorityId) -> Self {
+        SignedMessage {
+            context,
+            challenge,
+            authority_id,
+        }
+    }
+}
+
+fn pair() -> (ecdsa::Pair, AccountId32) {
+    let pair = ecdsa::Pair::from_seed(&[1; 32]);
+    let public = MultiSigner::from(pair.public()).into_account();
+    (pair, public)
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn register() {
+        let (pair, public) = pair();
+        let message = worst_case_message::<T, SignedMessage<_>>();
+        let registration = KeyRegistration {
+            public,
+            signature: message.sign(pair),
+            message,
+        };
+
+        let valid;
+        #[block]
+        {
+            valid = registration.is_valid();
+        }
+
+        assert!(valid);
+    }
+
+    #[benchmark]
+    fn authenticate() {
+        let (pair, mut public) = pair();
+        let message = worst_case_message::<T, SignedMessage<_>>();
+        let credential = KeySignature {
+            user_id: [0xff; 32],
+            signature: message.sign(pair),
+            message,
+        };
+
+        let verified;
+        #[block]
+        {
+            verified = public.verify(&credential);
+        }
+
+        assert!(verified.is_some());
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -28,6 +28,11 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(feature = "runtime")]
+pub mod weights;
+
 #[cfg(feature = "runtime")]
 mod runtime {
     use super::*;
@@ -44,6 +49,8 @@ mod runtime {
 
 #[cfg(feature = "runtime")]
 pub use runtime::{Authenticator, Device};
+#[cfg(feature = "runtime")]
+pub use weights::WeightInfo;
 
 mod signed_message;
 
@@ -83,6 +90,13 @@ pub struct KeySignature<Cx> {
     pub signature: MultiSignature,
 }
 
+/// Why a [`KeyRegistration`] or [`KeySignature`] failed to verify.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KeyError {
+    /// The signature doesn't verify against the public key.
+    InvalidSignature,
+}
+
 #[cfg(feature = "full-crypto")]
 /// Allows an arbitrary message to be signed from a signer which returns a [`MultiSignature`].
 pub trait Sign<S, Cx> {
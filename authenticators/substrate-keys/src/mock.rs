@@ -118,3 +118,12 @@ pub fn new_test_ext() -> TestExternalities {
     });
     t
 }
+
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::benchmarking::Config for Test {
+    type Context = BlockNumberFor<Test>;
+
+    fn worst_case_context() -> Self::Context {
+        u64::MAX
+    }
+}
@@ -1,7 +1,5 @@
 use super::*;
 
-use sp_runtime::traits::Verify;
-
 impl<Ch: Challenger, AuthId> From<KeyRegistration<CxOf<Ch>>> for Device<Ch, AuthId> {
     fn from(substrate_signature: KeyRegistration<CxOf<Ch>>) -> Self {
         Self::new(substrate_signature.public)
@@ -15,8 +13,9 @@ impl<Cx: Parameter + 'static> DeviceChallengeResponse<Cx> for KeyRegistration<Cx
             self.message.message().as_ref(),
             self.signature.encode(),
         );
-        self.signature
-            .verify(self.message.message().as_ref(), &self.public)
+        self.verify()
+            .inspect_err(|e| log::debug!(target: LOG_TARGET, "Invalid registration: {e:?}"))
+            .is_ok()
     }
 
     fn used_challenge(&self) -> (Cx, Challenge) {
@@ -1,5 +1,4 @@
 use super::*;
-use sp_runtime::traits::Verify;
 use traits_authn::UserChallengeResponse;
 
 impl<Cx: Parameter + 'static> UserChallengeResponse<Cx> for KeySignature<Cx> {
@@ -29,8 +28,8 @@ impl<Cx: Parameter> VerifyCredential<KeySignature<Cx>> for AccountId32 {
             credential.signature.encode(),
         );
         credential
-            .signature
-            .verify(credential.message.message().as_ref(), self)
-            .then_some(())
+            .verify(self)
+            .inspect_err(|e| log::debug!(target: LOG_TARGET, "Invalid signature: {e:?}"))
+            .ok()
     }
 }
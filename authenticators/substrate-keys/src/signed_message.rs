@@ -11,6 +11,32 @@ impl<Cx: Encode> SignedMessage<Cx> {
     }
 }
 
+#[cfg(feature = "runtime")]
+mod verification {
+    use super::*;
+    use sp_runtime::traits::Verify;
+
+    impl<Cx: Encode> KeyRegistration<Cx> {
+        /// Verifies that the registration is signed by the key being registered.
+        pub fn verify(&self) -> Result<(), KeyError> {
+            self.signature
+                .verify(self.message.message().as_ref(), &self.public)
+                .then_some(())
+                .ok_or(KeyError::InvalidSignature)
+        }
+    }
+
+    impl<Cx: Encode> KeySignature<Cx> {
+        /// Verifies that the credential is signed by `public`.
+        pub fn verify(&self, public: &AccountId32) -> Result<(), KeyError> {
+            self.signature
+                .verify(self.message.message().as_ref(), public)
+                .then_some(())
+                .ok_or(KeyError::InvalidSignature)
+        }
+    }
+}
+
 #[cfg(feature = "full-crypto")]
 mod signing {
     use super::*;
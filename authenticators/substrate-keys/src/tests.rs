@@ -1,5 +1,5 @@
 use crate::mock::*;
-use crate::{KeyRegistration, KeySignature, Sign, SignedMessage};
+use crate::{KeyError, KeyRegistration, KeySignature, Sign, SignedMessage};
 use frame::{
     deps::{
         sp_core::{sr25519, Pair},
@@ -159,3 +159,34 @@ mod authentication {
         })
     }
 }
+
+mod errors {
+    use super::*;
+
+    #[test]
+    fn tampered_messages_are_invalid_signatures() {
+        new_test_ext().execute_with(|| {
+            let (message, public, signature) = make_signature(&[]);
+
+            let mut registration = KeyRegistration {
+                public: public.clone(),
+                message: message.clone(),
+                signature: signature.clone(),
+            };
+            assert_eq!(registration.verify(), Ok(()));
+            registration.message.challenge = [0u8; 32];
+            assert_eq!(registration.verify(), Err(KeyError::InvalidSignature));
+
+            let credential = KeySignature {
+                user_id: USER,
+                message,
+                signature,
+            };
+            assert_eq!(credential.verify(&public), Ok(()));
+            assert_eq!(
+                credential.verify(&Keyring::Bob.to_account_id()),
+                Err(KeyError::InvalidSignature)
+            );
+        })
+    }
+}
@@ -0,0 +1,19 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pass_authenticators_webauthn::{
+    fuzzing::AuthenticatorData, AuthenticatorExtensionOutputs, CredentialPublicKey,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(authenticator_data) = AuthenticatorData::try_from(data) else {
+        return;
+    };
+
+    if let Some(attested_credential_data) = &authenticator_data.attested_credential_data {
+        let _ = CredentialPublicKey::try_from(&attested_credential_data.credential_public_key);
+    }
+    if let Some(extensions) = &authenticator_data.extensions {
+        let _ = AuthenticatorExtensionOutputs::try_from(extensions);
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pass_authenticators_webauthn::fuzzing::RawClientData;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(client_data) = RawClientData::try_from(data.to_vec()) {
+        let _ = client_data.challenge();
+        let _ = client_data.request_type();
+    }
+});
@@ -0,0 +1,30 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use webauthn_verifier::{webauthn_verify_with_algorithm, Algorithm};
+
+#[derive(Arbitrary, Debug)]
+struct Input<'a> {
+    algorithm: u8,
+    authenticator_data: &'a [u8],
+    client_data_json: &'a [u8],
+    signature: &'a [u8],
+    public_key_der: &'a [u8],
+}
+
+fuzz_target!(|input: Input| {
+    let algorithm = match input.algorithm % 3 {
+        0 => Algorithm::Es256,
+        1 => Algorithm::EdDsa,
+        _ => Algorithm::Rs256,
+    };
+
+    let _ = webauthn_verify_with_algorithm(
+        algorithm,
+        input.authenticator_data,
+        input.client_data_json,
+        input.signature,
+        input.public_key_der,
+    );
+});
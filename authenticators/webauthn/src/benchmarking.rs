@@ -0,0 +1,180 @@
+//! Benchmarks of the WebAuthn authenticator, with ES256 passkeys and the largest ceremonies: a
+//! `clientDataJSON` of [`MAX_CLIENT_DATA_LEN`] bytes and, on registration, a credential ID of
+//! [`MAX_CREDENTIAL_ID_LEN`] bytes with the costliest attestation statement accepted. That's a
+//! `tpm` statement signed by an RSA-4096 AIK, whose `x5c` holds [`MAX_TRUST_PATH_LEN`]
+//! certificates issued with P-384 keys, the slowest to verify. Ceremonies are verified against
+//! the default [`Config`](crate::Config) policies, trusting the root of that chain.
+
+use super::*;
+use alloc::{format, vec};
+pub use authenticator_benchmarking::Config;
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+use frame::{
+    benchmarking::prelude::*,
+    hashing::{blake2_256, sha2_256},
+};
+use p256::ecdsa::{signature::Signer, Signature, SigningKey};
+use traits_authn::{
+    util::VerifyCredential, Challenge, DeviceChallengeResponse, UserChallengeResponse,
+};
+
+const ORIGIN: &str = "https://pass.example.com";
+const RP_ID: &str = "pass.example.com";
+
+/// The `tpm` registration of the credential of [`signing_key`], made with the `clientDataJSON`
+/// of [`client_data`]. See `fixtures/attestation/README.md`.
+const TPM_LONGEST_CHAIN: &[u8] = include_bytes!("../fixtures/attestation/tpm-longest-chain.cbor");
+const LONGEST_CHAIN_ROOT: &[u8] = include_bytes!("../fixtures/attestation/longest-chain-root.der");
+
+pub struct Pallet<T: Config>(frame_system::Pallet<T>);
+
+/// The default policies, trusting the root of [`TPM_LONGEST_CHAIN`] at a time its certificates
+/// are valid at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LongestChain;
+
+impl crate::Config for LongestChain {
+    fn is_public_suffix(domain: &str) -> bool {
+        <() as crate::Config>::is_public_suffix(domain)
+    }
+
+    fn trust_anchors() -> Vec<TrustAnchor> {
+        vec![LONGEST_CHAIN_ROOT.to_vec()]
+    }
+
+    fn unix_time() -> Option<u64> {
+        // 2030-01-01
+        Some(1_893_456_000)
+    }
+}
+
+fn signing_key() -> SigningKey {
+    SigningKey::from_slice(&[1; 32]).expect("valid key")
+}
+
+fn public_key(signing_key: &SigningKey) -> CredentialPublicKey {
+    let point = signing_key.verifying_key().to_encoded_point(false);
+    CredentialPublicKey::es256(
+        point.x().expect("uncompressed"),
+        point.y().expect("uncompressed"),
+    )
+    .expect("valid coordinates")
+}
+
+/// A `clientDataJSON` padded up to [`MAX_CLIENT_DATA_LEN`] with a member clients may add.
+fn client_data(
+    request_type: &str,
+    challenge: &Challenge,
+) -> BoundedVec<u8, ConstU32<MAX_CLIENT_DATA_LEN>> {
+    let mut client_data = format!(
+        r#"{{"type":"{request_type}","challenge":"{}","origin":"{ORIGIN}","crossOrigin":false,"padding":""#,
+        base64::encode_engine(challenge, &BASE64_URL_SAFE_NO_PAD)
+    );
+    let padding = MAX_CLIENT_DATA_LEN as usize - client_data.len() - 2;
+    client_data.push_str(&"a".repeat(padding));
+    client_data.push_str(r#""}"#);
+    client_data
+        .into_bytes()
+        .try_into()
+        .expect("client data fits")
+}
+
+fn authenticator_data(flags: u8, sign_count: u32) -> Vec<u8> {
+    [
+        &sha2_256(RP_ID.as_bytes())[..],
+        &[flags],
+        &sign_count.to_be_bytes(),
+    ]
+    .concat()
+}
+
+fn sign(signing_key: &SigningKey, authenticator_data: &[u8], client_data: &[u8]) -> Vec<u8> {
+    let signature: Signature =
+        signing_key.sign(&[authenticator_data, &sha2_256(client_data)].concat());
+    signature.to_der().as_bytes().to_vec()
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn register() {
+        let credential_id = [0xff; MAX_CREDENTIAL_ID_LEN as usize];
+        let registration = Attestation::<T::Context, LongestChain> {
+            meta: AttestationMeta {
+                authority_id: [0xff; 32],
+                device_id: blake2_256(&credential_id),
+                context: T::worst_case_context(),
+            },
+            user_handle: [0xff; 32],
+            client_data: client_data("webauthn.create", &[0xff; 32]),
+            public_key: public_key(&signing_key()),
+            attestation_object: TPM_LONGEST_CHAIN
+                .to_vec()
+                .try_into()
+                .expect("attestation object fits"),
+            client_extensions: Default::default(),
+            transports: Default::default(),
+            _config: PhantomData,
+        };
+
+        let valid;
+        #[block]
+        {
+            valid = registration.is_valid();
+        }
+
+        assert!(valid);
+    }
+
+    #[benchmark]
+    fn authenticate() {
+        let signing_key = signing_key();
+        let public_key = public_key(&signing_key);
+
+        // UP and UV
+        let authenticator_data = authenticator_data(0x05, 1);
+        let client_data = client_data("webauthn.get", &[0xff; 32]);
+        let signature = sign(&signing_key, &authenticator_data, &client_data);
+        let credential = Assertion::<T::Context> {
+            meta: AssertionMeta {
+                authority_id: [0xff; 32],
+                user_id: [0xff; 32],
+                context: T::worst_case_context(),
+            },
+            authenticator_data,
+            client_data,
+            signature,
+            user_handle: Some([0xff; 32]),
+            _config: PhantomData,
+        };
+        let mut record = CredentialRecord {
+            device_id: [0xff; 32],
+            public_key,
+            sign_count: 0,
+            backup_eligible: false,
+            backup_state: false,
+            version: CREDENTIAL_RECORD_VERSION,
+            rp_id_hash: Some(sha2_256(RP_ID.as_bytes())),
+            reported_resident_key: None,
+            aaguid: None,
+            model: None,
+            credential_id: None,
+            transports: Default::default(),
+            created_at: None,
+            last_used_at: None,
+            user_handle: Some([0xff; 32]),
+        };
+
+        let valid;
+        #[block]
+        {
+            valid = credential.is_valid() && record.verify(&credential).is_some();
+        }
+
+        assert!(valid);
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
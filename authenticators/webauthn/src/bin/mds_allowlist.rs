@@ -0,0 +1,41 @@
+//! Converts a FIDO Metadata Service (MDS3) BLOB into the SCALE-encoded allowlist of
+//! authenticator models, as `Vec<(Aaguid, AuthenticatorModel)>`, once its signature is
+//! verified against the FIDO root certificate.
+//!
+//! Usage: `mds-allowlist <blob.jwt> <root.der>`
+
+use codec::Encode;
+use pass_authenticators_webauthn::mds;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    let (Some(blob_path), Some(root_path)) = (std::env::args().nth(1), std::env::args().nth(2))
+    else {
+        eprintln!("Usage: mds-allowlist <blob.jwt> <root.der>");
+        std::process::exit(1);
+    };
+    let blob = std::fs::read_to_string(&blob_path).unwrap_or_else(|e| {
+        eprintln!("Can't read {blob_path}: {e}");
+        std::process::exit(1);
+    });
+    let root = std::fs::read(&root_path).unwrap_or_else(|e| {
+        eprintln!("Can't read {root_path}: {e}");
+        std::process::exit(1);
+    });
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("the clock is past the Unix epoch")
+        .as_secs();
+    let allowlist = mds::allowlist(&blob, &root, now).unwrap_or_else(|e| {
+        eprintln!("Invalid metadata BLOB: {e:?}");
+        std::process::exit(1);
+    });
+
+    eprintln!("{} authenticator models", allowlist.len());
+    let encoded: String = allowlist
+        .encode()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    println!("0x{encoded}");
+}
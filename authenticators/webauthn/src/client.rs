@@ -0,0 +1,410 @@
+//! Client-side helpers to produce [`Attestation`]s and [`Assertion`]s out of the JSON
+//! serialization of WebAuthn credentials (`PublicKeyCredential.toJSON()`), and to request
+//! those credentials for an on-chain [`Challenge`].
+
+use super::*;
+
+use alloc::{string::String, vec::Vec};
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+use serde::{Deserialize, Serialize};
+use traits_authn::Challenge;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The credential is not valid JSON, or misses required fields.
+    InvalidJson,
+    /// A binary field is not valid base64url.
+    InvalidBase64,
+    /// The credential doesn't expose its public key, which happens when the client doesn't
+    /// support its algorithm.
+    MissingPublicKey,
+    /// The credential public key uses an unsupported algorithm, or has the wrong size.
+    UnsupportedPublicKey,
+    /// The client data exceeds the size the runtime accepts.
+    ClientDataTooLong,
+    /// The attestation object exceeds the size the runtime accepts.
+    AttestationObjectTooLong,
+    /// The credential returned no `userHandle`, so it's not discoverable.
+    MissingUserHandle,
+    /// The `userHandle` is not a `HashedUserId`.
+    InvalidUserHandle,
+}
+
+/// `RegistrationResponseJSON`, what `navigator.credentials.create()` resolves to, serialized.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistrationResponseJson {
+    pub raw_id: String,
+    pub response: AuthenticatorAttestationResponseJson,
+    #[serde(default)]
+    pub client_extension_results: ClientExtensionResultsJson,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticatorAttestationResponseJson {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    pub attestation_object: String,
+    pub public_key: Option<String>,
+    pub public_key_algorithm: i64,
+    #[serde(default)]
+    pub transports: Vec<String>,
+}
+
+/// The `clientExtensionResults` the runtime cares about.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientExtensionResultsJson {
+    pub cred_props: Option<CredPropsJson>,
+    pub prf: Option<PrfJson>,
+    pub large_blob: Option<LargeBlobJson>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CredPropsJson {
+    pub rk: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PrfJson {
+    pub enabled: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct LargeBlobJson {
+    pub supported: Option<bool>,
+}
+
+impl From<ClientExtensionResultsJson> for ClientExtensionOutputs {
+    fn from(results: ClientExtensionResultsJson) -> Self {
+        Self {
+            resident_key: results.cred_props.and_then(|cred_props| cred_props.rk),
+            prf_enabled: results.prf.and_then(|prf| prf.enabled),
+            large_blob_supported: results
+                .large_blob
+                .and_then(|large_blob| large_blob.supported),
+        }
+    }
+}
+
+/// `AuthenticationResponseJSON`, what `navigator.credentials.get()` resolves to, serialized.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticationResponseJson {
+    pub raw_id: String,
+    pub response: AuthenticatorAssertionResponseJson,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticatorAssertionResponseJson {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    pub authenticator_data: String,
+    pub signature: String,
+    pub user_handle: Option<String>,
+}
+
+impl<Cx, P> Attestation<Cx, P> {
+    /// Builds the attestation of a newly created credential, given as `RegistrationResponseJSON`.
+    /// `user_handle` is the `user.id` of the creation options.
+    pub fn from_registration_json(
+        json: &str,
+        authority_id: AuthorityId,
+        user_handle: HashedUserId,
+        context: Cx,
+    ) -> Result<Self, Error> {
+        let credential: RegistrationResponseJson =
+            serde_json::from_str(json).map_err(|_| Error::InvalidJson)?;
+        let response = credential.response;
+
+        let algorithm = Algorithm::from_cose(response.public_key_algorithm)
+            .ok_or(Error::UnsupportedPublicKey)?;
+        let public_key = decode(&response.public_key.ok_or(Error::MissingPublicKey)?)?;
+        let public_key = match algorithm {
+            Algorithm::Es256 => public_key.try_into().map(CredentialPublicKey::Es256).ok(),
+            Algorithm::EdDsa => public_key.try_into().map(CredentialPublicKey::EdDsa).ok(),
+            Algorithm::Rs256 => public_key.try_into().map(CredentialPublicKey::Rs256).ok(),
+        }
+        .ok_or(Error::UnsupportedPublicKey)?;
+
+        Ok(Self {
+            meta: AttestationMeta {
+                authority_id,
+                device_id: device_id(&decode(&credential.raw_id)?),
+                context,
+            },
+            user_handle,
+            client_data: client_data(&response.client_data_json)?,
+            public_key,
+            attestation_object: attestation_object(&response.attestation_object)?,
+            client_extensions: credential.client_extension_results.into(),
+            transports: transports(&response.transports),
+            _config: PhantomData,
+        })
+    }
+}
+
+impl<Cx, P> Assertion<Cx, P> {
+    /// Builds the assertion of `user_id`, given as `AuthenticationResponseJSON`.
+    pub fn from_authentication_json(
+        json: &str,
+        authority_id: AuthorityId,
+        user_id: HashedUserId,
+        context: Cx,
+    ) -> Result<Self, Error> {
+        let credential: AuthenticationResponseJson =
+            serde_json::from_str(json).map_err(|_| Error::InvalidJson)?;
+        Self::from_response(credential.response, authority_id, user_id, context)
+    }
+
+    /// Builds the assertion of a discoverable credential, given as
+    /// `AuthenticationResponseJSON`, for the user its `userHandle` names. This allows
+    /// usernameless logins.
+    pub fn from_discoverable_authentication_json(
+        json: &str,
+        authority_id: AuthorityId,
+        context: Cx,
+    ) -> Result<Self, Error> {
+        let credential: AuthenticationResponseJson =
+            serde_json::from_str(json).map_err(|_| Error::InvalidJson)?;
+        let user_id = user_handle(&credential.response)?.ok_or(Error::MissingUserHandle)?;
+        Self::from_response(credential.response, authority_id, user_id, context)
+    }
+
+    fn from_response(
+        response: AuthenticatorAssertionResponseJson,
+        authority_id: AuthorityId,
+        user_id: HashedUserId,
+        context: Cx,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            meta: AssertionMeta {
+                authority_id,
+                user_id,
+                context,
+            },
+            authenticator_data: decode(&response.authenticator_data)?,
+            client_data: client_data(&response.client_data_json)?,
+            signature: decode(&response.signature)?,
+            user_handle: user_handle(&response)?,
+            _config: PhantomData,
+        })
+    }
+}
+
+/// The `userHandle` of a discoverable credential, which is the `user.id` it was created with.
+fn user_handle(
+    response: &AuthenticatorAssertionResponseJson,
+) -> Result<Option<HashedUserId>, Error> {
+    response
+        .user_handle
+        .as_deref()
+        .map(|user_handle| {
+            decode(user_handle)?
+                .try_into()
+                .map_err(|_| Error::InvalidUserHandle)
+        })
+        .transpose()
+}
+
+/// The transports the runtime knows of, skipping unknown and repeated ones.
+fn transports(names: &[String]) -> Transports {
+    let mut transports = Vec::new();
+    for transport in names.iter().map(String::as_str).filter_map(transport) {
+        if !transports.contains(&transport) {
+            transports.push(transport);
+        }
+    }
+    bounded_transports(transports)
+}
+
+#[cfg(not(feature = "runtime"))]
+fn bounded_transports(transports: Vec<AuthenticatorTransport>) -> Transports {
+    transports
+}
+
+#[cfg(feature = "runtime")]
+fn bounded_transports(transports: Vec<AuthenticatorTransport>) -> Transports {
+    // There can't be more than `MAX_TRANSPORTS` different ones.
+    Transports::truncate_from(transports)
+}
+
+fn transport(name: &str) -> Option<AuthenticatorTransport> {
+    Some(match name {
+        "usb" => AuthenticatorTransport::Usb,
+        "nfc" => AuthenticatorTransport::Nfc,
+        "ble" => AuthenticatorTransport::Ble,
+        "smart-card" => AuthenticatorTransport::SmartCard,
+        "hybrid" => AuthenticatorTransport::Hybrid,
+        "internal" => AuthenticatorTransport::Internal,
+        _ => return None,
+    })
+}
+
+/// The `DeviceId` a credential is registered under: the BLAKE2-256 hash of its raw ID.
+pub fn device_id(credential_id: &[u8]) -> DeviceId {
+    sp_crypto_hashing::blake2_256(credential_id)
+}
+
+fn decode(value: &str) -> Result<Vec<u8>, Error> {
+    base64::decode_engine(value, &BASE64_URL_SAFE_NO_PAD).map_err(|_| Error::InvalidBase64)
+}
+
+fn encode(value: &[u8]) -> String {
+    base64::encode_engine(value, &BASE64_URL_SAFE_NO_PAD)
+}
+
+#[cfg(not(feature = "runtime"))]
+fn client_data(client_data_json: &str) -> Result<Vec<u8>, Error> {
+    decode(client_data_json)
+}
+
+#[cfg(feature = "runtime")]
+fn client_data(
+    client_data_json: &str,
+) -> Result<BoundedVec<u8, ConstU32<MAX_CLIENT_DATA_LEN>>, Error> {
+    decode(client_data_json)?
+        .try_into()
+        .map_err(|_| Error::ClientDataTooLong)
+}
+
+#[cfg(not(feature = "runtime"))]
+fn attestation_object(attestation_object: &str) -> Result<Vec<u8>, Error> {
+    decode(attestation_object)
+}
+
+#[cfg(feature = "runtime")]
+fn attestation_object(
+    attestation_object: &str,
+) -> Result<BoundedVec<u8, ConstU32<MAX_ATTESTATION_OBJECT_LEN>>, Error> {
+    decode(attestation_object)?
+        .try_into()
+        .map_err(|_| Error::AttestationObjectTooLong)
+}
+
+/// `PublicKeyCredentialCreationOptionsJSON`, to be passed to
+/// `PublicKeyCredential.parseCreationOptionsFromJSON()`.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreationOptions {
+    pub rp: RelyingParty,
+    pub user: User,
+    pub challenge: String,
+    pub pub_key_cred_params: Vec<CredentialParameters>,
+    pub authenticator_selection: AuthenticatorSelection,
+    /// `none`, `indirect`, `direct` or `enterprise`.
+    pub attestation: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RelyingParty {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    pub id: String,
+    pub name: String,
+    pub display_name: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CredentialParameters {
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub alg: i64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticatorSelection {
+    pub resident_key: String,
+    pub user_verification: UserVerificationRequirement,
+}
+
+impl CreationOptions {
+    /// Options to create a passkey for `user_id` on `rp_id` that answers `challenge`.
+    ///
+    /// They ask for user verification and for direct attestation, and list every algorithm
+    /// the authenticator supports.
+    pub fn new(
+        rp_id: &str,
+        user_id: &HashedUserId,
+        user_name: &str,
+        challenge: &Challenge,
+    ) -> Self {
+        Self {
+            rp: RelyingParty {
+                id: rp_id.into(),
+                name: rp_id.into(),
+            },
+            user: User {
+                id: encode(user_id),
+                name: user_name.into(),
+                display_name: user_name.into(),
+            },
+            challenge: encode(challenge),
+            pub_key_cred_params: [Algorithm::Es256, Algorithm::EdDsa, Algorithm::Rs256]
+                .iter()
+                .map(|algorithm| CredentialParameters {
+                    ty: "public-key".into(),
+                    alg: algorithm.cose(),
+                })
+                .collect(),
+            authenticator_selection: AuthenticatorSelection {
+                resident_key: "preferred".into(),
+                user_verification: UserVerificationRequirement::Required,
+            },
+            attestation: "direct".into(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("options are serializable; qed")
+    }
+}
+
+/// `PublicKeyCredentialRequestOptionsJSON`, to be passed to
+/// `PublicKeyCredential.parseRequestOptionsFromJSON()`.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestOptions {
+    pub rp_id: String,
+    pub challenge: String,
+    pub allow_credentials: Vec<CredentialDescriptor>,
+    pub user_verification: UserVerificationRequirement,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CredentialDescriptor {
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub id: String,
+}
+
+impl RequestOptions {
+    /// Options to sign `challenge` with one of `credential_ids` (or any discoverable
+    /// credential if empty) for `rp_id`.
+    pub fn new(rp_id: &str, challenge: &Challenge, credential_ids: &[&[u8]]) -> Self {
+        Self {
+            rp_id: rp_id.into(),
+            challenge: encode(challenge),
+            allow_credentials: credential_ids
+                .iter()
+                .map(|id| CredentialDescriptor {
+                    ty: "public-key".into(),
+                    id: encode(id),
+                })
+                .collect(),
+            user_verification: UserVerificationRequirement::Required,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("options are serializable; qed")
+    }
+}
@@ -3,7 +3,8 @@
 extern crate alloc;
 
 use alloc::vec::Vec;
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, Input};
+use core::marker::PhantomData;
 use traits_authn::{AuthorityId, DeviceId, HashedUserId};
 #[cfg(feature = "runtime")]
 use {
@@ -13,32 +14,252 @@ use {
     scale_info::TypeInfo,
 };
 
+#[cfg(feature = "client")]
+pub mod client;
+#[cfg(all(feature = "client", feature = "runtime"))]
+pub mod mds;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
 #[cfg(feature = "runtime")]
-pub use runtime::{Authenticator, Device};
+pub mod weights;
+
+#[cfg(feature = "fuzzing")]
+pub use runtime::fuzzing;
+#[cfg(feature = "runtime")]
+pub use runtime::{
+    attestation_statement::{AttestationFormat, AttestationType, VerifiedAttestation},
+    config::{AllowedOrigin, BackupStatePolicy, Config, SignCountPolicy, TrustAnchor},
+    credential_record::SignCountCheck,
+    error::WebAuthnError,
+    extensions::{AuthenticatorExtensionOutputs, CredProtect},
+    Authenticator, Device,
+};
+#[cfg(feature = "runtime")]
+pub use weights::WeightInfo;
 #[cfg(feature = "runtime")]
 mod runtime;
 
+pub use webauthn_verifier::{Algorithm, PreparedKey, VerifyError};
+
 pub type DEREncodedPublicKey = [u8; 91];
+pub type DEREncodedEd25519PublicKey = [u8; 44];
+/// Up to a 4096-bit modulus.
+pub const MAX_RSA_PUBLIC_KEY_LEN: u32 = 550;
+#[cfg(not(feature = "runtime"))]
+pub type DEREncodedRsaPublicKey = Vec<u8>;
+#[cfg(feature = "runtime")]
+pub type DEREncodedRsaPublicKey = BoundedVec<u8, ConstU32<MAX_RSA_PUBLIC_KEY_LEN>>;
 
-#[derive(Clone, Encode, Decode, Debug, PartialEq, Eq, Copy)]
-#[cfg_attr(
-    feature = "runtime",
-    derive(DecodeWithMemTracking, TypeInfo, MaxEncodedLen)
-)]
+/// Longest `clientDataJSON` ceremonies may have.
+pub const MAX_CLIENT_DATA_LEN: u32 = 1024;
+
+/// Longest `attestationObject` registrations may carry. It fits the longest credential ID and
+/// RSA key, along with an attestation statement with a chain of a few RSA certificates.
+pub const MAX_ATTESTATION_OBJECT_LEN: u32 = 8 * 1024;
+
+/// Most certificates the `x5c` of an attestation statement may hold. Each one is checked
+/// against the next, so this bounds the cost of a registration.
+pub const MAX_TRUST_PATH_LEN: u32 = 5;
+
+/// Longest credential ID authenticators may return.
+pub const MAX_CREDENTIAL_ID_LEN: u32 = 1023;
+#[cfg(not(feature = "runtime"))]
+pub type CredentialId = Vec<u8>;
+#[cfg(feature = "runtime")]
+pub type CredentialId = BoundedVec<u8, ConstU32<MAX_CREDENTIAL_ID_LEN>>;
+
+/// The DER-encoded `SubjectPublicKeyInfo` of a credential, tagged with the COSE algorithm the
+/// credential signs with.
+#[derive(Clone, Encode, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "runtime", derive(TypeInfo, MaxEncodedLen))]
+pub enum CredentialPublicKey {
+    #[codec(index = 0)]
+    Es256(DEREncodedPublicKey),
+    #[codec(index = 1)]
+    EdDsa(DEREncodedEd25519PublicKey),
+    #[codec(index = 2)]
+    Rs256(DEREncodedRsaPublicKey),
+}
+
+impl CredentialPublicKey {
+    pub fn algorithm(&self) -> Algorithm {
+        match self {
+            Self::Es256(_) => Algorithm::Es256,
+            Self::EdDsa(_) => Algorithm::EdDsa,
+            Self::Rs256(_) => Algorithm::Rs256,
+        }
+    }
+
+    pub fn as_der(&self) -> &[u8] {
+        match self {
+            Self::Es256(key) => key.as_slice(),
+            Self::EdDsa(key) => key.as_slice(),
+            Self::Rs256(key) => key.as_slice(),
+        }
+    }
+
+    /// Decodes the key, to verify many signatures with.
+    pub fn prepare(&self) -> Result<PreparedKey, VerifyError> {
+        PreparedKey::from_der(self.algorithm(), self.as_der())
+    }
+}
+
+/// A DER-encoded SPKI starts with the `SEQUENCE` tag.
+const DER_SEQUENCE_TAG: u8 = 0x30;
+
+impl CredentialPublicKey {
+    /// Decodes a key, telling whether it was stored in the legacy, untagged format.
+    fn decode_with_format<I: Input>(input: &mut I) -> Result<(Self, bool), codec::Error> {
+        match input.read_byte()? {
+            0 => Ok((Self::Es256(Decode::decode(input)?), false)),
+            1 => Ok((Self::EdDsa(Decode::decode(input)?), false)),
+            2 => Ok((Self::Rs256(Decode::decode(input)?), false)),
+            // Records created before algorithms were tagged hold a bare P-256 key, so
+            // fall back to ES256.
+            DER_SEQUENCE_TAG => {
+                let mut key = [0u8; 91];
+                key[0] = DER_SEQUENCE_TAG;
+                input.read(&mut key[1..])?;
+                Ok((Self::Es256(key), true))
+            }
+            _ => Err("Invalid CredentialPublicKey: unknown variant".into()),
+        }
+    }
+}
+
+impl Decode for CredentialPublicKey {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+        Self::decode_with_format(input).map(|(key, _)| key)
+    }
+}
+
+#[cfg(feature = "runtime")]
+impl DecodeWithMemTracking for CredentialPublicKey {}
+
+/// The version records are encoded with. Legacy records, which predate it, have none.
+pub(crate) const CREDENTIAL_RECORD_VERSION: u8 = 1;
+
+#[derive(Clone, Encode, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "runtime", derive(TypeInfo, MaxEncodedLen))]
 pub struct CredentialRecord {
     device_id: DeviceId,
-    //. A DER-encoded public key
-    public_key: DEREncodedPublicKey,
+    public_key: CredentialPublicKey,
     sign_count: u32,
     backup_eligible: bool,
     backup_state: bool,
+    /// Always [`CREDENTIAL_RECORD_VERSION`] once decoded, as older records are upgraded when
+    /// re-encoded.
+    version: u8,
+    /// The `rpIdHash` the credential was registered with. Legacy records don't have it.
+    rp_id_hash: Option<[u8; 32]>,
+    /// Whether the credential is discoverable, as the client reported through `credProps`.
+    /// Nothing signs it, so it's only a hint, never a basis for policy.
+    reported_resident_key: Option<bool>,
+    /// The AAGUID of the authenticator the credential was created on.
+    aaguid: Option<Aaguid>,
+    /// The model of that authenticator, if the runtime knew it on registration.
+    model: Option<AuthenticatorModel>,
+    /// The credential ID, whose hash is `device_id`. Legacy records don't have it.
+    credential_id: Option<CredentialId>,
+    /// The transports the authenticator reported on registration.
+    transports: Transports,
+    /// The block the credential was registered at.
+    created_at: Option<u32>,
+    /// The block the credential was last used at.
+    last_used_at: Option<u32>,
+    /// The `user.id` the credential was created with, which discoverable credentials return
+    /// as `userHandle`. Legacy records don't have it.
+    user_handle: Option<HashedUserId>,
 }
 
+impl CredentialRecord {
+    /// The decoded public key of the credential, which verifiers of many assertions can
+    /// cache to verify them with `verify_assertion_with_key`.
+    pub fn prepared_key(&self) -> Result<PreparedKey, VerifyError> {
+        self.public_key.prepare()
+    }
+
+    /// The credential ID, as clients list it in `allowCredentials`.
+    pub fn credential_id(&self) -> Option<&[u8]> {
+        self.credential_id.as_deref()
+    }
+
+    /// The AAGUID of the authenticator the credential was created on.
+    pub fn aaguid(&self) -> Option<&Aaguid> {
+        self.aaguid.as_ref()
+    }
+
+    /// The model of the authenticator the credential was created on, if the runtime knew it.
+    pub fn model(&self) -> Option<&AuthenticatorModel> {
+        self.model.as_ref()
+    }
+
+    /// The transports clients can reach the authenticator through.
+    pub fn transports(&self) -> &[AuthenticatorTransport] {
+        &self.transports
+    }
+
+    /// The block the credential was registered at.
+    pub fn created_at(&self) -> Option<u32> {
+        self.created_at
+    }
+
+    /// The block the credential was last used at.
+    pub fn last_used_at(&self) -> Option<u32> {
+        self.last_used_at
+    }
+}
+
+impl Decode for CredentialRecord {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+        let device_id = Decode::decode(input)?;
+        let (public_key, legacy) = CredentialPublicKey::decode_with_format(input)?;
+        let mut record = Self {
+            device_id,
+            public_key,
+            sign_count: Decode::decode(input)?,
+            backup_eligible: Decode::decode(input)?,
+            backup_state: Decode::decode(input)?,
+            version: CREDENTIAL_RECORD_VERSION,
+            rp_id_hash: None,
+            reported_resident_key: None,
+            aaguid: None,
+            model: None,
+            credential_id: None,
+            transports: Default::default(),
+            created_at: None,
+            last_used_at: None,
+            user_handle: None,
+        };
+        // Legacy records end here.
+        if legacy {
+            return Ok(record);
+        }
+
+        if input.read_byte()? != CREDENTIAL_RECORD_VERSION {
+            return Err("Invalid CredentialRecord: unknown version".into());
+        }
+        record.rp_id_hash = Decode::decode(input)?;
+        record.reported_resident_key = Decode::decode(input)?;
+        record.aaguid = Decode::decode(input)?;
+        record.model = Decode::decode(input)?;
+        record.credential_id = Decode::decode(input)?;
+        record.transports = Decode::decode(input)?;
+        record.created_at = Decode::decode(input)?;
+        record.last_used_at = Decode::decode(input)?;
+        record.user_handle = Decode::decode(input)?;
+        Ok(record)
+    }
+}
+
+#[cfg(feature = "runtime")]
+impl DecodeWithMemTracking for CredentialRecord {}
+
 #[derive(Clone, Encode, Decode, Debug, PartialEq, Eq, Copy)]
 #[cfg_attr(
     feature = "runtime",
@@ -50,16 +271,125 @@ pub struct AttestationMeta<Cx> {
     pub(crate) context: Cx,
 }
 
+/// Whether a ceremony needs user verification, see
+/// [`UserVerificationRequirement`](https://www.w3.org/TR/webauthn-3/#enumdef-userverificationrequirement).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "client",
+    derive(serde::Serialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum UserVerificationRequirement {
+    Required,
+    Preferred,
+    Discouraged,
+}
+
+impl UserVerificationRequirement {
+    /// Whether a ceremony, performed with or without user verification, meets the requirement.
+    /// Only `Required` is enforced; the others just tell clients what to ask for.
+    pub fn is_satisfied_by(&self, user_verified: bool) -> bool {
+        user_verified || *self != Self::Required
+    }
+}
+
+/// The client extension outputs (`getClientExtensionResults()`) of a registration. Unlike
+/// authenticator extension outputs, these are not signed.
+#[derive(Clone, Copy, Default, Encode, Decode, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "runtime",
+    derive(DecodeWithMemTracking, TypeInfo, MaxEncodedLen)
+)]
+pub struct ClientExtensionOutputs {
+    /// `credProps.rk`: whether the credential is discoverable, as the client claims.
+    pub resident_key: Option<bool>,
+    /// `prf.enabled`
+    pub prf_enabled: Option<bool>,
+    /// `largeBlob.supported`
+    pub large_blob_supported: Option<bool>,
+}
+
+/// How clients can reach an authenticator, see
+/// [`AuthenticatorTransport`](https://www.w3.org/TR/webauthn-3/#enumdef-authenticatortransport).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(
+    feature = "runtime",
+    derive(DecodeWithMemTracking, TypeInfo, MaxEncodedLen)
+)]
+pub enum AuthenticatorTransport {
+    Usb,
+    Nfc,
+    Ble,
+    SmartCard,
+    Hybrid,
+    Internal,
+}
+
+/// As many as there are transports.
+pub const MAX_TRANSPORTS: u32 = 6;
+#[cfg(not(feature = "runtime"))]
+pub type Transports = Vec<AuthenticatorTransport>;
+#[cfg(feature = "runtime")]
+pub type Transports = BoundedVec<AuthenticatorTransport, ConstU32<MAX_TRANSPORTS>>;
+
+/// The AAGUID of an authenticator, which identifies its model.
+pub type Aaguid = [u8; 16];
+
+/// The highest certification of an authenticator model, as reported by the
+/// [FIDO Metadata Service](https://fidoalliance.org/metadata/).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Encode, Decode)]
+#[cfg_attr(
+    feature = "runtime",
+    derive(DecodeWithMemTracking, TypeInfo, MaxEncodedLen)
+)]
+pub enum CertificationLevel {
+    NotCertified,
+    SelfAsserted,
+    L1,
+    L1Plus,
+    L2,
+    L2Plus,
+    L3,
+    L3Plus,
+}
+
+/// What the runtime knows about an authenticator model.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(
+    feature = "runtime",
+    derive(DecodeWithMemTracking, TypeInfo, MaxEncodedLen)
+)]
+pub struct AuthenticatorModel {
+    pub certification: CertificationLevel,
+    /// Whether the model has been revoked, or its keys compromised.
+    pub revoked: bool,
+}
+
+/// `P` is the runtime [`Config`] the attestation is verified against.
 #[derive(Clone, Encode, Decode, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "runtime", derive(DecodeWithMemTracking, TypeInfo))]
-pub struct Attestation<Cx> {
+#[cfg_attr(feature = "runtime", scale_info(skip_type_params(P)))]
+pub struct Attestation<Cx, P = ()> {
     pub(crate) meta: AttestationMeta<Cx>,
-    pub(crate) authenticator_data: Vec<u8>,
+    /// The `user.id` of the creation options.
+    pub(crate) user_handle: HashedUserId,
     #[cfg(not(feature = "runtime"))]
     pub(crate) client_data: Vec<u8>,
     #[cfg(feature = "runtime")]
-    pub(crate) client_data: BoundedVec<u8, ConstU32<1024>>,
-    pub(crate) public_key: DEREncodedPublicKey,
+    pub(crate) client_data: BoundedVec<u8, ConstU32<MAX_CLIENT_DATA_LEN>>,
+    pub(crate) public_key: CredentialPublicKey,
+    /// The CBOR-encoded `attestationObject`, holding the attestation statement and the
+    /// `authenticatorData` it attests.
+    #[cfg(not(feature = "runtime"))]
+    pub(crate) attestation_object: Vec<u8>,
+    #[cfg(feature = "runtime")]
+    pub(crate) attestation_object: BoundedVec<u8, ConstU32<MAX_ATTESTATION_OBJECT_LEN>>,
+    pub(crate) client_extensions: ClientExtensionOutputs,
+    /// The transports the authenticator reports (`getTransports()`). Like client extension
+    /// outputs, these are not signed.
+    pub(crate) transports: Transports,
+    #[codec(skip)]
+    pub(crate) _config: PhantomData<P>,
 }
 
 #[derive(Clone, Encode, Decode, Debug, PartialEq, Eq, Copy)]
@@ -73,14 +403,21 @@ pub struct AssertionMeta<Cx> {
     pub(crate) context: Cx,
 }
 
+/// `P` is the runtime [`Config`] the assertion is verified against.
 #[derive(Clone, Encode, Decode, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "runtime", derive(DecodeWithMemTracking, TypeInfo))]
-pub struct Assertion<Cx> {
+#[cfg_attr(feature = "runtime", scale_info(skip_type_params(P)))]
+pub struct Assertion<Cx, P = ()> {
     pub(crate) meta: AssertionMeta<Cx>,
     pub(crate) authenticator_data: Vec<u8>,
     #[cfg(not(feature = "runtime"))]
     pub(crate) client_data: Vec<u8>,
     #[cfg(feature = "runtime")]
-    pub(crate) client_data: BoundedVec<u8, ConstU32<1024>>,
+    pub(crate) client_data: BoundedVec<u8, ConstU32<MAX_CLIENT_DATA_LEN>>,
     pub(crate) signature: Vec<u8>,
+    /// The `userHandle` returned by discoverable credentials, which must be the `user.id`
+    /// the credential was created with.
+    pub(crate) user_handle: Option<HashedUserId>,
+    #[codec(skip)]
+    pub(crate) _config: PhantomData<P>,
 }
@@ -0,0 +1,204 @@
+//! Builds the compact allowlist of authenticator models the runtime looks up in
+//! `Config::authenticator_model`, out of a BLOB of the
+//! [FIDO Metadata Service](https://fidoalliance.org/metadata/) (MDS3).
+//!
+//! The BLOB is a JWT, signed by a certificate its `x5c` header chains up to the FIDO root
+//! certificate, [GlobalSign Root CA - R3](https://secure.globalsign.com/cacert/root-r3.crt).
+
+use super::*;
+
+use crate::runtime::attestation_statement::{public_key_der, verify_chain};
+use alloc::{string::String, vec::Vec};
+use base64::prelude::{BASE64_STANDARD, BASE64_URL_SAFE_NO_PAD};
+use serde::Deserialize;
+use x509_cert::{der::Decode as _, Certificate};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The BLOB is not a JWT.
+    MalformedJwt,
+    /// A part of the JWT is not valid base64url.
+    InvalidBase64,
+    /// The JWT header is not an `RS256` one with an `x5c` certificate chain.
+    InvalidHeader,
+    /// The given FIDO root certificate is not DER-encoded.
+    InvalidRoot,
+    /// The `x5c` chain doesn't lead to the FIDO root, or isn't currently valid.
+    UntrustedChain,
+    /// The JWT signature doesn't verify with the key of the first `x5c` certificate.
+    InvalidSignature,
+    /// The JWT payload is not a metadata BLOB.
+    InvalidJson,
+    /// An entry has a malformed AAGUID.
+    InvalidAaguid,
+}
+
+/// `MetadataBLOBPayload`, with only the fields the allowlist needs.
+#[derive(Clone, Debug, Deserialize)]
+pub struct MetadataBlobPayload {
+    pub entries: Vec<MetadataBlobPayloadEntry>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataBlobPayloadEntry {
+    /// Only FIDO2 authenticators have one.
+    pub aaguid: Option<String>,
+    #[serde(default)]
+    pub status_reports: Vec<StatusReport>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusReport {
+    pub status: String,
+    /// `YYYY-MM-DD`. Reports without it predate every dated one.
+    pub effective_date: Option<String>,
+}
+
+/// The JWT header, with only the fields the signature is verified with.
+#[derive(Clone, Debug, Deserialize)]
+struct JwtHeader {
+    alg: String,
+    /// Base64-encoded DER certificates, the first one holding the signing key.
+    x5c: Vec<String>,
+}
+
+/// Statuses that make an authenticator model untrustworthy.
+const REVOKED_STATUSES: [&str; 5] = [
+    "REVOKED",
+    "USER_VERIFICATION_BYPASS",
+    "ATTESTATION_KEY_COMPROMISE",
+    "USER_KEY_REMOTE_COMPROMISE",
+    "USER_KEY_PHYSICAL_COMPROMISE",
+];
+
+/// The models of the FIDO2 authenticators a metadata BLOB lists, sorted by AAGUID.
+///
+/// `fido_root` is the DER-encoded FIDO root certificate, and `now` (in seconds since the Unix
+/// epoch) the time the `x5c` chain must be valid at.
+pub fn allowlist(
+    blob: &str,
+    fido_root: &[u8],
+    now: u64,
+) -> Result<Vec<(Aaguid, AuthenticatorModel)>, Error> {
+    let [header, payload, signature] = blob
+        .trim()
+        .split('.')
+        .collect::<Vec<_>>()
+        .try_into()
+        .map_err(|_| Error::MalformedJwt)?;
+    verify_jwt(header, payload, signature, fido_root, now)?;
+    payload_allowlist(payload)
+}
+
+/// The allowlist of a base64url-encoded JWT payload, whose signature is already verified.
+pub(crate) fn payload_allowlist(payload: &str) -> Result<Vec<(Aaguid, AuthenticatorModel)>, Error> {
+    let payload = base64::decode_engine(payload, &BASE64_URL_SAFE_NO_PAD)
+        .map_err(|_| Error::InvalidBase64)?;
+    let payload: MetadataBlobPayload =
+        serde_json::from_slice(&payload).map_err(|_| Error::InvalidJson)?;
+
+    let mut allowlist = payload
+        .entries
+        .iter()
+        .filter_map(|entry| Some((entry.aaguid.as_deref()?, entry)))
+        .map(|(aaguid, entry)| Ok((parse_aaguid(aaguid)?, model(&entry.status_reports))))
+        .collect::<Result<Vec<_>, _>>()?;
+    allowlist.sort_by_key(|(aaguid, _)| *aaguid);
+    Ok(allowlist)
+}
+
+/// Verifies the signature of a JWT with the key of the first `x5c` certificate, once the
+/// chain is known to lead to `fido_root`.
+fn verify_jwt(
+    header: &str,
+    payload: &str,
+    signature: &str,
+    fido_root: &[u8],
+    now: u64,
+) -> Result<(), Error> {
+    let decoded_header =
+        base64::decode_engine(header, &BASE64_URL_SAFE_NO_PAD).map_err(|_| Error::InvalidBase64)?;
+    let decoded_header: JwtHeader =
+        serde_json::from_slice(&decoded_header).map_err(|_| Error::InvalidHeader)?;
+    // FIDO signs BLOBs with RSA keys.
+    if decoded_header.alg != "RS256" {
+        return Err(Error::InvalidHeader);
+    }
+    let x5c = decoded_header
+        .x5c
+        .iter()
+        .map(|cert| {
+            let der = base64::decode_engine(cert, &BASE64_STANDARD).ok()?;
+            Certificate::from_der(&der).ok()
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or(Error::InvalidHeader)?;
+    let fido_root = Certificate::from_der(fido_root).map_err(|_| Error::InvalidRoot)?;
+    verify_chain(&x5c, &[fido_root], Some(now))
+        .filter(|trusted| *trusted)
+        .ok_or(Error::UntrustedChain)?;
+
+    let signature = base64::decode_engine(signature, &BASE64_URL_SAFE_NO_PAD)
+        .map_err(|_| Error::InvalidBase64)?;
+    let public_key = x5c
+        .first()
+        .and_then(public_key_der)
+        .ok_or(Error::InvalidHeader)?;
+    webauthn_verifier::verify_signature(
+        Algorithm::Rs256,
+        format!("{header}.{payload}").as_bytes(),
+        &signature,
+        &public_key,
+    )
+    .map_err(|_| Error::InvalidSignature)
+}
+
+/// Parses an AAGUID in its `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx` form.
+fn parse_aaguid(aaguid: &str) -> Result<Aaguid, Error> {
+    let hex: Vec<u8> = aaguid.bytes().filter(|c| *c != b'-').collect();
+    if hex.len() != 32 {
+        return Err(Error::InvalidAaguid);
+    }
+
+    let mut parsed = Aaguid::default();
+    for (byte, digits) in parsed.iter_mut().zip(hex.chunks(2)) {
+        let digits = core::str::from_utf8(digits).map_err(|_| Error::InvalidAaguid)?;
+        *byte = u8::from_str_radix(digits, 16).map_err(|_| Error::InvalidAaguid)?;
+    }
+    Ok(parsed)
+}
+
+/// The latest certification in the status reports of a model, and whether the latest report
+/// revokes it.
+fn model(status_reports: &[StatusReport]) -> AuthenticatorModel {
+    // Stable, so that reports of the same date keep their order.
+    let mut status_reports: Vec<_> = status_reports.iter().collect();
+    status_reports.sort_by_key(|report| report.effective_date.as_deref());
+
+    AuthenticatorModel {
+        certification: status_reports
+            .iter()
+            .rev()
+            .find_map(|report| certification_level(&report.status))
+            .unwrap_or(CertificationLevel::NotCertified),
+        revoked: status_reports
+            .last()
+            .is_some_and(|report| REVOKED_STATUSES.contains(&report.status.as_str())),
+    }
+}
+
+fn certification_level(status: &str) -> Option<CertificationLevel> {
+    Some(match status {
+        "NOT_FIDO_CERTIFIED" => CertificationLevel::NotCertified,
+        "SELF_ASSERTION_SUBMITTED" => CertificationLevel::SelfAsserted,
+        "FIDO_CERTIFIED" | "FIDO_CERTIFIED_L1" => CertificationLevel::L1,
+        "FIDO_CERTIFIED_L1plus" => CertificationLevel::L1Plus,
+        "FIDO_CERTIFIED_L2" => CertificationLevel::L2,
+        "FIDO_CERTIFIED_L2plus" => CertificationLevel::L2Plus,
+        "FIDO_CERTIFIED_L3" => CertificationLevel::L3,
+        "FIDO_CERTIFIED_L3plus" => CertificationLevel::L3Plus,
+        _ => return None,
+    })
+}
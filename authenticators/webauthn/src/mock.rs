@@ -1,6 +1,11 @@
 //! Test environment for pass webauthn.
 
-use crate::{AssertionMeta, Authenticator, DEREncodedPublicKey};
+use crate::{
+    AssertionMeta, Authenticator, CredentialPublicKey, DEREncodedPublicKey,
+    MAX_ATTESTATION_OBJECT_LEN,
+};
+use core::marker::PhantomData;
+use coset::{cbor::Value, CborSerializable};
 use frame::{
     testing_prelude::*,
     traits::{ConstU32, EqualPrivilegeOnly},
@@ -152,7 +157,7 @@ impl WebAuthnClient {
         &mut self,
         user_id: HashedUserId,
         challenge: impl Into<Bytes>,
-    ) -> Result<(Vec<u8>, Vec<u8>, Vec<u8>, DEREncodedPublicKey), ()> {
+    ) -> Result<(Vec<u8>, Vec<u8>, Vec<u8>, DEREncodedPublicKey, Vec<u8>), ()> {
         let creation_options = CredentialCreationOptions {
             public_key: PublicKeyCredentialCreationOptions {
                 rp: PublicKeyCredentialRpEntity {
@@ -201,6 +206,7 @@ impl WebAuthnClient {
             result.response.authenticator_data.into(),
             result.response.client_data_json.into(),
             public_key,
+            result.response.attestation_object.into(),
         ))
     }
 
@@ -248,16 +254,16 @@ impl WebAuthnClient {
         Ok((user_handle, authenticator_data, client_data, signature))
     }
 
-    pub fn attestation(
+    pub fn attestation<P>(
         &mut self,
         user_id: HashedUserId,
         context: BlockNumberFor<Test>,
         xtc: &impl ExtrinsicContext,
         authority_id: traits_authn::AuthorityId,
-    ) -> (Vec<u8>, crate::Attestation<BlockNumberFor<Test>>) {
+    ) -> (Vec<u8>, crate::Attestation<BlockNumberFor<Test>, P>) {
         let challenge = BlockChallenger::generate(&context, xtc);
 
-        let (credential_id, authenticator_data, client_data, public_key) = self
+        let (credential_id, _, client_data, public_key, attestation_object) = self
             .create_credential_sync(user_id, challenge.as_slice())
             .expect("Failed creating credential");
 
@@ -269,40 +275,88 @@ impl WebAuthnClient {
                     device_id: blake2_256(&credential_id),
                     context,
                 },
-                authenticator_data,
+                user_handle: user_id,
                 client_data: BoundedVec::try_from(client_data)
                     .expect("client_data is long enough; qed"),
-                public_key,
+                public_key: CredentialPublicKey::Es256(public_key),
+                attestation_object: BoundedVec::try_from(attestation_object)
+                    .expect("attestation_object is long enough; qed"),
+                client_extensions: Default::default(),
+                transports: Default::default(),
+                _config: PhantomData,
             },
         )
     }
 
-    pub fn assertion(
+    pub fn assertion<P>(
         &mut self,
         credential_id: impl Into<Bytes>,
         context: BlockNumberFor<Test>,
         xtc: &impl ExtrinsicContext,
         authority_id: traits_authn::AuthorityId,
-    ) -> crate::Assertion<BlockNumberFor<Test>> {
+    ) -> crate::Assertion<BlockNumberFor<Test>, P> {
         let challenge = BlockChallenger::generate(&context, xtc);
 
         let (user_handle, authenticator_data, client_data, signature) = self
             .authenticate_credential_sync(credential_id, challenge.as_slice())
             .expect("Failed retrieving credential");
 
+        let user_id: HashedUserId = Decode::decode(&mut TrailingZeroInput::new(&user_handle))
+            .expect(
+                "`user_handle` corresponds to the `user_id` inserted when creating credential; qed",
+            );
+
         crate::Assertion {
             meta: AssertionMeta {
                 authority_id,
-                user_id: Decode::decode(&mut TrailingZeroInput::new(&user_handle)).expect("`user_handle` corresponds to the `user_id` inserted when creating credential; qed"),
+                user_id,
                 context,
             },
             authenticator_data,
-            client_data: BoundedVec::try_from(client_data).expect("client_data is long enough; qed"),
+            client_data: BoundedVec::try_from(client_data)
+                .expect("client_data is long enough; qed"),
             signature,
+            user_handle: Some(user_id),
+            _config: PhantomData,
         }
     }
 }
 
+/// The `key` entry of an `attestationObject`.
+pub fn attestation_object_entry(attestation_object: &[u8], key: &str) -> Value {
+    Value::from_slice(attestation_object)
+        .ok()
+        .and_then(|object| object.into_map().ok())
+        .and_then(|entries| {
+            entries
+                .into_iter()
+                .find_map(|(k, value)| (k.as_text() == Some(key)).then_some(value))
+        })
+        .expect("attestation objects hold every entry; qed")
+}
+
+/// `attestation_object`, with its `key` entry replaced by `value`.
+pub fn with_attestation_object_entry(
+    attestation_object: &[u8],
+    key: &str,
+    value: Value,
+) -> BoundedVec<u8, ConstU32<MAX_ATTESTATION_OBJECT_LEN>> {
+    let mut entries = Value::from_slice(attestation_object)
+        .ok()
+        .and_then(|object| object.into_map().ok())
+        .expect("attestation objects are CBOR maps; qed");
+    for (k, v) in entries.iter_mut() {
+        if k.as_text() == Some(key) {
+            *v = value.clone();
+        }
+    }
+    Value::Map(entries)
+        .to_vec()
+        .ok()
+        .and_then(|object| BoundedVec::try_from(object).ok())
+        .expect("attestation_object is long enough; qed")
+}
+
 pub struct TestExt(pub TestExternalities, pub WebAuthnClient);
 impl TestExt {
     pub fn execute_with<R>(&mut self, execute: impl FnOnce(&mut WebAuthnClient) -> R) -> R {
@@ -320,3 +374,12 @@ pub fn new_test_ext(times: usize, with_signer_counter: bool) -> TestExt {
         WebAuthnClient::new("https://pass_web.pass.int", times, with_signer_counter),
     )
 }
+
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::benchmarking::Config for Test {
+    type Context = BlockNumberFor<Test>;
+
+    fn worst_case_context() -> Self::Context {
+        u64::MAX
+    }
+}
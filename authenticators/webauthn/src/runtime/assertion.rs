@@ -4,37 +4,77 @@ use crate::runtime::client_data::RawClientData;
 use frame::deps::sp_core::hexdisplay::AsBytesRef;
 use traits_authn::{HashedUserId, UserChallengeResponse};
 
-impl<Cx> Assertion<Cx>
+impl<Cx, P> Assertion<Cx, P>
 where
     Cx: Parameter,
+    P: Config,
 {
     fn challenge(&self) -> Challenge {
         TryInto::<RawClientData>::try_into(self.client_data.to_vec())
             .map(|client_data| client_data.challenge().unwrap_or_default())
             .unwrap_or_default()
     }
+
+    /// Verifies the assertion as an authentication ceremony. The signature is verified
+    /// against the credential, by [`CredentialRecord::verify_assertion`].
+    pub fn verify(&self) -> Result<(), WebAuthnError> {
+        let client_data: RawClientData = self
+            .client_data
+            .to_vec()
+            .try_into()
+            .map_err(|_| WebAuthnError::InvalidClientData)?;
+        let authenticator_data: AuthenticatorData = self
+            .authenticator_data
+            .as_bytes_ref()
+            .try_into()
+            .map_err(|_| WebAuthnError::InvalidAuthenticatorData)?;
+
+        // clientData.type == "webauthn.get"
+        if client_data.request_type() != "webauthn.get" {
+            return Err(WebAuthnError::WrongRequestType);
+        }
+
+        // The origin is allowed for the authority, and rpIdHash matches the RP ID it claims
+        let rp_id_hash = config::rp_id_hash::<P>(&self.meta.authority_id, &client_data.origin)?;
+        if authenticator_data.rp_id_hash != rp_id_hash {
+            return Err(WebAuthnError::RpIdMismatch);
+        }
+        // Cross-origin ceremonies are embedded by an allowed top-level origin
+        config::check_cross_origin::<P>(
+            &self.meta.authority_id,
+            client_data.cross_origin,
+            client_data.top_origin.as_deref(),
+        )?;
+
+        // Check UP, and UV if the runtime requires it
+        if !authenticator_data
+            .flags
+            .contains(AuthenticatorFlags::USER_PRESENT)
+        {
+            return Err(WebAuthnError::UserNotPresent);
+        }
+        if !P::authentication_user_verification().is_satisfied_by(
+            authenticator_data
+                .flags
+                .contains(AuthenticatorFlags::USER_VERIFIED),
+        ) {
+            return Err(WebAuthnError::UserNotVerified);
+        }
+
+        // Extension outputs comply with the runtime policies
+        extensions::verify::<P>(authenticator_data.extensions.as_ref(), false)
+    }
 }
 
-impl<Cx> UserChallengeResponse<Cx> for Assertion<Cx>
+impl<Cx, P> UserChallengeResponse<Cx> for Assertion<Cx, P>
 where
     Cx: Parameter + Copy + 'static,
+    P: Config,
 {
     fn is_valid(&self) -> bool {
-        let Ok(client_data): Result<RawClientData, _> = self.client_data.to_vec().try_into() else {
-            return false;
-        };
-        let Ok(authenticator_data): Result<AuthenticatorData, _> =
-            self.authenticator_data.as_bytes_ref().try_into()
-        else {
-            return false;
-        };
-
-        // clientData.type == "webauthn.create"
-        client_data.request_type().eq(&String::from("webauthn.get"))
-            // Check UP
-            && authenticator_data
-                .flags
-                .contains(AuthenticatorFlags::USER_PRESENT)
+        self.verify()
+            .inspect_err(|e| log::debug!(target: LOG_TARGET, "Invalid assertion: {e:?}"))
+            .is_ok()
     }
 
     fn used_challenge(&self) -> (Cx, Challenge) {
@@ -1,72 +1,162 @@
 use super::*;
 use crate::runtime::authenticator_data::AuthenticatorFlags;
+use attestation_statement::{AttestationObject, VerifiedAttestation};
 use authenticator_data::AuthenticatorData;
 use client_data::RawClientData;
-use frame::deps::sp_core::hexdisplay::AsBytesRef;
-use frame::hashing::sha2_256;
-use url::Url;
+use config::BackupStatePolicy;
+use frame::hashing::blake2_256;
 
-impl<Cx> Attestation<Cx>
+impl<Cx, P> Attestation<Cx, P>
 where
     Cx: Parameter,
+    P: Config,
 {
     fn challenge(&self) -> Challenge {
         TryInto::<RawClientData>::try_into(self.client_data.to_vec())
             .map(|client_data| client_data.challenge().unwrap_or_default())
             .unwrap_or_default()
     }
-}
 
-#[cfg(any(feature = "runtime", test))]
-impl<Cx> DeviceChallengeResponse<Cx> for Attestation<Cx>
-where
-    Cx: Parameter + Copy + 'static,
-{
-    fn is_valid(&self) -> bool {
-        let Ok(client_data): Result<RawClientData, _> = self.client_data.to_vec().try_into() else {
-            return false;
-        };
-        let Ok(authenticator_data): Result<AuthenticatorData, _> =
-            self.authenticator_data.as_bytes_ref().try_into()
-        else {
-            return false;
-        };
-
-        let rp_id_hash = {
-            let Ok(origin_url) = Url::parse(&client_data.origin) else {
-                return false;
-            };
-            let Some(domain) = origin_url.domain() else {
-                return false;
-            };
-
-            sha2_256(domain.as_bytes())
-        };
+    /// The `authenticatorData` attested in `attestationObject`.
+    pub fn authenticator_data(&self) -> Option<Vec<u8>> {
+        AttestationObject::parse(&self.attestation_object).map(|object| object.auth_data)
+    }
+
+    /// Verifies the attestation statement against the trust anchors of `P`.
+    pub fn attestation_statement(&self) -> Option<VerifiedAttestation> {
+        AttestationObject::parse(&self.attestation_object)?.verify_with::<P>(&self.client_data)
+    }
+
+    /// Verifies the attestation as a registration ceremony, returning its (already allowed)
+    /// attestation statement.
+    pub fn verify(&self) -> Result<VerifiedAttestation, WebAuthnError> {
+        let client_data: RawClientData = self
+            .client_data
+            .to_vec()
+            .try_into()
+            .map_err(|_| WebAuthnError::InvalidClientData)?;
+        let attestation_object = AttestationObject::parse(&self.attestation_object)
+            .ok_or(WebAuthnError::InvalidAttestationObject)?;
+        let authenticator_data: AuthenticatorData = attestation_object
+            .auth_data
+            .as_slice()
+            .try_into()
+            .map_err(|_| WebAuthnError::InvalidAuthenticatorData)?;
 
         // clientData.type == "webauthn.create"
-        client_data
-            .request_type()
-            .eq(&String::from("webauthn.create"))
-            // rpIdHash == sha256(origin.domain)
-            && authenticator_data.rp_id_hash == rp_id_hash
-            // if !BE, then !BS
-            && (
-                authenticator_data
-                    .flags
-                    .contains(AuthenticatorFlags::BACKUP_ELEGIBILITY) ||
-                !authenticator_data
-                    .flags
-                    .contains(AuthenticatorFlags::BACKUP_STATE)
-            )
-            // UP && UV
+        if client_data.request_type() != "webauthn.create" {
+            return Err(WebAuthnError::WrongRequestType);
+        }
+
+        // The origin is allowed for the authority, and rpIdHash matches the RP ID it claims
+        let rp_id_hash = config::rp_id_hash::<P>(&self.meta.authority_id, &client_data.origin)?;
+        if authenticator_data.rp_id_hash != rp_id_hash {
+            return Err(WebAuthnError::RpIdMismatch);
+        }
+        // Cross-origin ceremonies are embedded by an allowed top-level origin
+        config::check_cross_origin::<P>(
+            &self.meta.authority_id,
+            client_data.cross_origin,
+            client_data.top_origin.as_deref(),
+        )?;
+
+        // if !BE, then !BS
+        if !authenticator_data
+            .flags
+            .contains(AuthenticatorFlags::BACKUP_ELEGIBILITY)
             && authenticator_data
                 .flags
-                .contains(AuthenticatorFlags::USER_PRESENT)
+                .contains(AuthenticatorFlags::BACKUP_STATE)
+        {
+            return Err(WebAuthnError::BackupStateWithoutEligibility);
+        }
+        // BE is allowed by the backup state policy
+        if P::backup_state_policy() == BackupStatePolicy::SingleDevice
             && authenticator_data
                 .flags
-                .contains(AuthenticatorFlags::USER_VERIFIED)
-            // Len(CredentialID) < 1023
-            && authenticator_data.attested_credential_data.is_some_and(|att_credential| att_credential.credential_id.len() < 1023)
+                .contains(AuthenticatorFlags::BACKUP_ELEGIBILITY)
+        {
+            return Err(WebAuthnError::BackupEligibleNotAllowed);
+        }
+
+        // UP, and UV if the runtime requires it
+        if !authenticator_data
+            .flags
+            .contains(AuthenticatorFlags::USER_PRESENT)
+        {
+            return Err(WebAuthnError::UserNotPresent);
+        }
+        if !P::registration_user_verification().is_satisfied_by(
+            authenticator_data
+                .flags
+                .contains(AuthenticatorFlags::USER_VERIFIED),
+        ) {
+            return Err(WebAuthnError::UserNotVerified);
+        }
+
+        let att_credential = authenticator_data
+            .attested_credential_data
+            .as_ref()
+            .ok_or(WebAuthnError::MissingAttestedCredential)?;
+        // Len(CredentialID) <= 1023
+        if att_credential.credential_id.len() > MAX_CREDENTIAL_ID_LEN as usize {
+            return Err(WebAuthnError::CredentialIdTooLong);
+        }
+        // The device is registered under the hash of the credential ID, so nobody can claim
+        // the device ID of a credential they don't own
+        if self.meta.device_id != blake2_256(att_credential.credential_id) {
+            return Err(WebAuthnError::DeviceIdMismatch);
+        }
+        // The given public key is the one attested in authenticatorData
+        if CredentialPublicKey::try_from(&att_credential.credential_public_key)
+            .ok()
+            .is_none_or(|public_key| public_key != self.public_key)
+        {
+            return Err(WebAuthnError::PublicKeyMismatch);
+        }
+
+        // Extension outputs comply with the runtime policies
+        extensions::verify::<P>(authenticator_data.extensions.as_ref(), true)?;
+
+        // The attestation statement verifies, and the runtime accepts it
+        let attestation = attestation_object
+            .verify_with::<P>(&self.client_data)
+            .ok_or(WebAuthnError::InvalidAttestationStatement)?;
+        if !P::allow_attestation(&attestation) {
+            return Err(WebAuthnError::AttestationNotAllowed);
+        }
+
+        // The runtime accepts the authenticator model
+        let aaguid = att_credential.aaguid;
+        if !P::allow_authenticator(&aaguid, P::authenticator_model(&aaguid).as_ref()) {
+            return Err(WebAuthnError::AuthenticatorNotAllowed);
+        }
+
+        Ok(attestation)
+    }
+}
+
+impl AttestationObject {
+    /// Verifies the statement against the trust anchors of `P`, at its current time.
+    fn verify_with<P: Config>(&self, client_data: &[u8]) -> Option<VerifiedAttestation> {
+        self.verify(
+            client_data,
+            &attestation_statement::parse_trust_anchors(&P::trust_anchors()),
+            P::unix_time(),
+        )
+    }
+}
+
+#[cfg(any(feature = "runtime", test))]
+impl<Cx, P> DeviceChallengeResponse<Cx> for Attestation<Cx, P>
+where
+    Cx: Parameter + Copy + 'static,
+    P: Config,
+{
+    fn is_valid(&self) -> bool {
+        self.verify()
+            .inspect_err(|e| log::debug!(target: LOG_TARGET, "Invalid attestation: {e:?}"))
+            .is_ok()
     }
 
     fn used_challenge(&self) -> (Cx, Challenge) {
@@ -0,0 +1,768 @@
+//! Verification of attestation statements, as defined in
+//! [§8 Defined Attestation Statement Formats](https://www.w3.org/TR/webauthn-3/#sctn-defined-attestation-formats).
+
+use super::*;
+
+use alloc::{string::String, vec::Vec};
+use authenticator_data::AuthenticatorData;
+use config::TrustAnchor;
+use coset::{cbor::Value, CborSerializable};
+use frame::hashing::sha2_256;
+use sha2::{Digest, Sha256, Sha384};
+use webauthn_verifier::verify_signature;
+use x509_cert::{
+    certificate::Version,
+    der::{
+        asn1::{ObjectIdentifier, OctetStringRef},
+        Decode as _, Encode as _,
+    },
+    ext::{
+        pkix::{BasicConstraints, ExtendedKeyUsage, KeyUsage},
+        Extension,
+    },
+    Certificate,
+};
+
+const ID_AT_COMMON_NAME: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.4.3");
+const ID_AT_COUNTRY_NAME: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.4.6");
+const ID_AT_ORGANIZATION_NAME: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.4.10");
+const ID_AT_ORGANIZATIONAL_UNIT_NAME: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.4.11");
+const ID_CE_BASIC_CONSTRAINTS: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.29.19");
+const ID_CE_KEY_USAGE: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.29.15");
+const ID_CE_SUBJECT_ALT_NAME: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.29.17");
+const ID_CE_EXT_KEY_USAGE: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.29.37");
+const ID_FIDO_GEN_CE_AAGUID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.4.1.45724.1.1.4");
+const ID_APPLE_NONCE: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113635.100.8.2");
+const ID_ANDROID_KEY_DESCRIPTION: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.4.1.11129.2.1.17");
+const TCG_KP_AIK_CERTIFICATE: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.23.133.8.3");
+const ECDSA_WITH_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+const ECDSA_WITH_SHA384: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.3");
+const SHA256_WITH_RSA_ENCRYPTION: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.11");
+
+/// The format of an attestation statement (`fmt`).
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum AttestationFormat {
+    None,
+    Packed,
+    FidoU2f,
+    Apple,
+    AndroidKey,
+    Tpm,
+}
+
+/// See [§6.5.4 Attestation Types](https://www.w3.org/TR/webauthn-3/#sctn-attestation-types).
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum AttestationType {
+    /// No information about the authenticator is given.
+    None,
+    /// The credential key signs its own attestation.
+    SelfAttestation,
+    /// A key shared by a batch of authenticators signs the attestation.
+    Basic,
+    /// An attestation key certified by a CA (e.g. a TPM's AIK) signs the attestation.
+    AttCa,
+    /// A CA certifies the credential key itself, keeping the authenticator anonymous.
+    AnonCa,
+}
+
+/// The result of verifying an attestation statement.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct VerifiedAttestation {
+    pub format: AttestationFormat,
+    pub attestation_type: AttestationType,
+    /// Whether the attestation certificate chain leads to one of [`Config::trust_anchors`].
+    pub trusted: bool,
+}
+
+impl VerifiedAttestation {
+    /// Whether a certificate chain trusted by the runtime vouches for the authenticator, as
+    /// opposed to self or no attestation, which any software can produce.
+    pub fn is_hardware_backed(&self) -> bool {
+        self.trusted
+            && matches!(
+                self.attestation_type,
+                AttestationType::Basic | AttestationType::AttCa | AttestationType::AnonCa
+            )
+    }
+}
+
+/// Parses DER-encoded trust anchors, skipping malformed ones. Attestations can then be
+/// [verified](verify) against them without parsing them again.
+pub fn parse_trust_anchors(trust_anchors: &[TrustAnchor]) -> Vec<Certificate> {
+    trust_anchors
+        .iter()
+        .filter_map(|anchor| {
+            Certificate::from_der(anchor)
+                .inspect_err(|e| log::warn!(target: LOG_TARGET, "Malformed trust anchor: {e:?}"))
+                .ok()
+        })
+        .collect()
+}
+
+/// Verifies the statement in `attestation_object` for the registration of the
+/// `authenticatorData` it holds and `client_data`, checking its certificate chain (if any)
+/// against `trust_anchors` at `now`, in seconds since the Unix epoch.
+///
+/// Returns `None` if the statement is malformed, unsupported or doesn't verify.
+pub fn verify(
+    attestation_object: &[u8],
+    client_data: &[u8],
+    trust_anchors: &[Certificate],
+    now: Option<u64>,
+) -> Option<VerifiedAttestation> {
+    AttestationObject::parse(attestation_object)?.verify(client_data, trust_anchors, now)
+}
+
+/// A decoded `attestationObject`.
+pub(crate) struct AttestationObject {
+    fmt: String,
+    statement: Statement,
+    /// The `authenticatorData` the statement attests.
+    pub auth_data: Vec<u8>,
+}
+
+impl AttestationObject {
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let Value::Map(entries) = Value::from_slice(bytes).ok()? else {
+            return None;
+        };
+        let (mut fmt, mut statement, mut auth_data) = (None, None, None);
+        for (key, value) in entries {
+            match (key.as_text()?, value) {
+                ("fmt", Value::Text(value)) => fmt = Some(value),
+                ("attStmt", Value::Map(value)) => statement = Some(value),
+                ("authData", Value::Bytes(value)) => auth_data = Some(value),
+                _ => return None,
+            }
+        }
+        Some(Self {
+            fmt: fmt?,
+            statement: statement?,
+            auth_data: auth_data?,
+        })
+    }
+
+    /// See [`verify`].
+    pub fn verify(
+        &self,
+        client_data: &[u8],
+        trust_anchors: &[Certificate],
+        now: Option<u64>,
+    ) -> Option<VerifiedAttestation> {
+        let statement = &self.statement;
+        let parsed_authenticator_data: AuthenticatorData =
+            self.auth_data.as_slice().try_into().ok()?;
+        let credential = parsed_authenticator_data
+            .attested_credential_data
+            .as_ref()?;
+        let ceremony = Ceremony {
+            authenticator_data: &self.auth_data,
+            client_data_hash: sha2_256(client_data),
+            rp_id_hash: parsed_authenticator_data.rp_id_hash,
+            aaguid: credential.aaguid,
+            credential_id: credential.credential_id,
+            credential_public_key: (&credential.credential_public_key).try_into().ok()?,
+        };
+
+        let (format, (attestation_type, trust_path)) = match self.fmt.as_str() {
+            "none" => (
+                AttestationFormat::None,
+                statement
+                    .is_empty()
+                    .then_some((AttestationType::None, Vec::new()))?,
+            ),
+            "packed" => (
+                AttestationFormat::Packed,
+                verify_packed(statement, &ceremony)?,
+            ),
+            "fido-u2f" => (
+                AttestationFormat::FidoU2f,
+                verify_fido_u2f(statement, &ceremony)?,
+            ),
+            "apple" => (
+                AttestationFormat::Apple,
+                verify_apple(statement, &ceremony)?,
+            ),
+            "android-key" => (
+                AttestationFormat::AndroidKey,
+                verify_android_key(statement, &ceremony)?,
+            ),
+            "tpm" => (AttestationFormat::Tpm, verify_tpm(statement, &ceremony)?),
+            _ => return None,
+        };
+
+        Some(VerifiedAttestation {
+            format,
+            attestation_type,
+            trusted: !trust_path.is_empty() && verify_chain(&trust_path, trust_anchors, now)?,
+        })
+    }
+}
+
+struct Ceremony<'a> {
+    authenticator_data: &'a [u8],
+    client_data_hash: [u8; 32],
+    rp_id_hash: [u8; 32],
+    aaguid: [u8; 16],
+    credential_id: &'a [u8],
+    credential_public_key: CredentialPublicKey,
+}
+
+impl Ceremony<'_> {
+    /// `authenticatorData || clientDataHash`, which most formats sign.
+    fn signed_data(&self) -> Vec<u8> {
+        [self.authenticator_data, &self.client_data_hash].concat()
+    }
+}
+
+type Statement = Vec<(Value, Value)>;
+type TrustPath = Vec<Certificate>;
+
+fn field<'a>(statement: &'a Statement, name: &str) -> Option<&'a Value> {
+    statement
+        .iter()
+        .find_map(|(key, value)| (key.as_text() == Some(name)).then_some(value))
+}
+
+fn bytes_field<'a>(statement: &'a Statement, name: &str) -> Option<&'a [u8]> {
+    field(statement, name)?.as_bytes().map(Vec::as_slice)
+}
+
+fn alg_field(statement: &Statement) -> Option<Algorithm> {
+    let alg = field(statement, "alg")?.as_integer()?;
+    Algorithm::from_cose(i64::try_from(alg).ok()?)
+}
+
+/// Parses `x5c`, an array of one to [`MAX_TRUST_PATH_LEN`] DER-encoded certificates, the first
+/// being the attestation certificate.
+fn x5c_field(statement: &Statement) -> Option<TrustPath> {
+    let x5c = field(statement, "x5c")?.as_array()?;
+    (1..=MAX_TRUST_PATH_LEN as usize)
+        .contains(&x5c.len())
+        .then_some(())?;
+    x5c.iter()
+        .map(|cert| Certificate::from_der(cert.as_bytes()?).ok())
+        .collect()
+}
+
+pub(crate) fn public_key_der(cert: &Certificate) -> Option<Vec<u8>> {
+    cert.tbs_certificate.subject_public_key_info.to_der().ok()
+}
+
+fn extension(cert: &Certificate, oid: ObjectIdentifier) -> Option<&Extension> {
+    cert.tbs_certificate
+        .extensions
+        .as_ref()?
+        .iter()
+        .find(|ext| ext.extn_id == oid)
+}
+
+/// Checks the requirements shared by attestation certificates: being a v3, non-CA
+/// certificate, and matching the AAGUID of the authenticator if it states one.
+fn check_attestation_certificate(cert: &Certificate, aaguid: &[u8; 16]) -> Option<()> {
+    (cert.tbs_certificate.version == Version::V3).then_some(())?;
+    if let Some(ext) = extension(cert, ID_CE_BASIC_CONSTRAINTS) {
+        let basic_constraints = BasicConstraints::from_der(ext.extn_value.as_bytes()).ok()?;
+        (!basic_constraints.ca).then_some(())?;
+    }
+    if let Some(ext) = extension(cert, ID_FIDO_GEN_CE_AAGUID) {
+        let cert_aaguid = OctetStringRef::from_der(ext.extn_value.as_bytes()).ok()?;
+        (!ext.critical && cert_aaguid.as_bytes() == aaguid).then_some(())?;
+    }
+    Some(())
+}
+
+/// [§8.2 Packed Attestation Statement Format](https://www.w3.org/TR/webauthn-3/#sctn-packed-attestation)
+fn verify_packed(
+    statement: &Statement,
+    ceremony: &Ceremony,
+) -> Option<(AttestationType, TrustPath)> {
+    let alg = alg_field(statement)?;
+    let sig = bytes_field(statement, "sig")?;
+
+    if field(statement, "x5c").is_none() {
+        // Self attestation
+        (alg == ceremony.credential_public_key.algorithm()).then_some(())?;
+        verify_signature(
+            alg,
+            &ceremony.signed_data(),
+            sig,
+            ceremony.credential_public_key.as_der(),
+        )
+        .ok()?;
+        return Some((AttestationType::SelfAttestation, Vec::new()));
+    }
+
+    let trust_path = x5c_field(statement)?;
+    let attestation_cert = &trust_path[0];
+    verify_signature(
+        alg,
+        &ceremony.signed_data(),
+        sig,
+        &public_key_der(attestation_cert)?,
+    )
+    .ok()?;
+    check_attestation_certificate(attestation_cert, &ceremony.aaguid)?;
+    // §8.2.1: the country of the vendor, its legal name, "Authenticator Attestation" and a name
+    // of its choosing
+    let subject = |oid| subject_attributes(attestation_cert, oid);
+    subject(ID_AT_COUNTRY_NAME)
+        .any(|c| c.len() == 2)
+        .then_some(())?;
+    subject(ID_AT_ORGANIZATION_NAME)
+        .any(|o| !o.is_empty())
+        .then_some(())?;
+    subject(ID_AT_ORGANIZATIONAL_UNIT_NAME)
+        .any(|ou| ou == b"Authenticator Attestation")
+        .then_some(())?;
+    subject(ID_AT_COMMON_NAME)
+        .any(|cn| !cn.is_empty())
+        .then_some(())?;
+
+    Some((AttestationType::Basic, trust_path))
+}
+
+/// The values of the attributes of the subject of `cert` of type `oid`.
+fn subject_attributes(cert: &Certificate, oid: ObjectIdentifier) -> impl Iterator<Item = &[u8]> {
+    cert.tbs_certificate
+        .subject
+        .0
+        .iter()
+        .flat_map(|rdn| rdn.0.iter())
+        .filter(move |attr| attr.oid == oid)
+        .map(|attr| attr.value.value())
+}
+
+/// [§8.6 FIDO U2F Attestation Statement Format](https://www.w3.org/TR/webauthn-3/#sctn-fido-u2f-attestation)
+fn verify_fido_u2f(
+    statement: &Statement,
+    ceremony: &Ceremony,
+) -> Option<(AttestationType, TrustPath)> {
+    let sig = bytes_field(statement, "sig")?;
+    let trust_path = x5c_field(statement)?;
+    (trust_path.len() == 1).then_some(())?;
+
+    // U2F credentials are P-256 keys, sent as an uncompressed point.
+    let CredentialPublicKey::Es256(credential_public_key) = &ceremony.credential_public_key else {
+        return None;
+    };
+    let verification_data = [
+        &[0x00],
+        &ceremony.rp_id_hash[..],
+        &ceremony.client_data_hash,
+        ceremony.credential_id,
+        &credential_public_key[26..],
+    ]
+    .concat();
+
+    // Fails unless the attestation certificate holds a P-256 key too.
+    verify_signature(
+        Algorithm::Es256,
+        &verification_data,
+        sig,
+        &public_key_der(&trust_path[0])?,
+    )
+    .ok()?;
+
+    Some((AttestationType::Basic, trust_path))
+}
+
+/// [§8.8 Apple Anonymous Attestation Statement Format](https://www.w3.org/TR/webauthn-3/#sctn-apple-anonymous-attestation)
+fn verify_apple(
+    statement: &Statement,
+    ceremony: &Ceremony,
+) -> Option<(AttestationType, TrustPath)> {
+    let trust_path = x5c_field(statement)?;
+    let credential_cert = &trust_path[0];
+
+    // The nonce is in `SEQUENCE { [1] EXPLICIT OCTET STRING }`.
+    let nonce = DerReader(
+        extension(credential_cert, ID_APPLE_NONCE)?
+            .extn_value
+            .as_bytes(),
+    )
+    .expect(SEQUENCE)?
+    .expect(0xa1)?
+    .expect(OCTET_STRING)?
+    .0;
+    (nonce == sha2_256(&ceremony.signed_data())).then_some(())?;
+    (public_key_der(credential_cert)? == ceremony.credential_public_key.as_der()).then_some(())?;
+
+    Some((AttestationType::AnonCa, trust_path))
+}
+
+/// [§8.4 Android Key Attestation Statement Format](https://www.w3.org/TR/webauthn-3/#sctn-android-key-attestation)
+fn verify_android_key(
+    statement: &Statement,
+    ceremony: &Ceremony,
+) -> Option<(AttestationType, TrustPath)> {
+    let alg = alg_field(statement)?;
+    let sig = bytes_field(statement, "sig")?;
+    let trust_path = x5c_field(statement)?;
+    let credential_cert = &trust_path[0];
+    let credential_cert_key = public_key_der(credential_cert)?;
+
+    verify_signature(alg, &ceremony.signed_data(), sig, &credential_cert_key).ok()?;
+    (credential_cert_key == ceremony.credential_public_key.as_der()).then_some(())?;
+    check_key_description(
+        extension(credential_cert, ID_ANDROID_KEY_DESCRIPTION)?
+            .extn_value
+            .as_bytes(),
+        &ceremony.client_data_hash,
+    )?;
+
+    Some((AttestationType::Basic, trust_path))
+}
+
+/// Checks the `KeyDescription` of an Android Keystore key: it must be bound to this ceremony,
+/// generated in the keystore, usable for signing and scoped to the calling app.
+fn check_key_description(key_description: &[u8], client_data_hash: &[u8; 32]) -> Option<()> {
+    const KM_PURPOSE_SIGN: u64 = 2;
+    const KM_ORIGIN_GENERATED: u64 = 0;
+
+    let mut description = DerReader(key_description).expect(SEQUENCE)?;
+    // attestationVersion, attestationSecurityLevel, keyMintVersion, keyMintSecurityLevel
+    for _ in 0..4 {
+        description.next()?;
+    }
+    (description.expect(OCTET_STRING)?.0 == client_data_hash).then_some(())?;
+    // uniqueId
+    description.next()?;
+
+    let mut signs = false;
+    let mut origin = None;
+    for _ in 0..2 {
+        // softwareEnforced, then hardwareEnforced
+        let mut authorizations = description.expect(SEQUENCE)?;
+        while let Some((tag, mut value)) = authorizations.next() {
+            match tag {
+                // purpose: [1] EXPLICIT SET OF INTEGER
+                1 => {
+                    let mut purposes = value.expect(SET)?;
+                    while !purposes.0.is_empty() {
+                        signs |= purposes.expect(INTEGER)?.uint()? == KM_PURPOSE_SIGN;
+                    }
+                }
+                // allApplications: [600] EXPLICIT NULL
+                600 => return None,
+                // origin: [702] EXPLICIT INTEGER
+                702 => origin = Some(value.expect(INTEGER)?.uint()?),
+                _ => {}
+            }
+        }
+    }
+
+    (signs && origin == Some(KM_ORIGIN_GENERATED)).then_some(())
+}
+
+/// [§8.3 TPM Attestation Statement Format](https://www.w3.org/TR/webauthn-3/#sctn-tpm-attestation)
+fn verify_tpm(statement: &Statement, ceremony: &Ceremony) -> Option<(AttestationType, TrustPath)> {
+    const TPM_GENERATED_VALUE: u32 = 0xff544347;
+    const TPM_ST_ATTEST_CERTIFY: u16 = 0x8017;
+
+    (field(statement, "ver")?.as_text()? == "2.0").then_some(())?;
+    let alg = alg_field(statement)?;
+    let sig = bytes_field(statement, "sig")?;
+    let cert_info = bytes_field(statement, "certInfo")?;
+    let pub_area = bytes_field(statement, "pubArea")?;
+    let trust_path = x5c_field(statement)?;
+
+    // `pubArea` must describe the credential key.
+    let (name_alg, pub_area_key) = parse_pub_area(pub_area)?;
+    (pub_area_key == ceremony.credential_public_key).then_some(())?;
+
+    // `certInfo` must certify `pubArea` for this ceremony.
+    let mut reader = TpmReader(cert_info);
+    (reader.u32()? == TPM_GENERATED_VALUE).then_some(())?;
+    (reader.u16()? == TPM_ST_ATTEST_CERTIFY).then_some(())?;
+    // qualifiedSigner
+    reader.sized()?;
+    let extra_data = reader.sized()?;
+    // clockInfo, firmwareVersion
+    reader.take(17 + 8)?;
+    let name = reader.sized()?;
+    // qualifiedName
+    reader.sized()?;
+    reader.0.is_empty().then_some(())?;
+
+    let att_to_be_signed_hash = match alg {
+        Algorithm::Es256 | Algorithm::Rs256 => sha2_256(&ceremony.signed_data()),
+        Algorithm::EdDsa => return None,
+    };
+    (extra_data == att_to_be_signed_hash).then_some(())?;
+    let pub_area_hash = match name_alg {
+        TPM_ALG_SHA256 => Sha256::digest(pub_area).to_vec(),
+        TPM_ALG_SHA384 => Sha384::digest(pub_area).to_vec(),
+        _ => return None,
+    };
+    (name == [&name_alg.to_be_bytes()[..], &pub_area_hash].concat()).then_some(())?;
+
+    let aik_cert = &trust_path[0];
+    verify_signature(alg, cert_info, sig, &public_key_der(aik_cert)?).ok()?;
+    check_attestation_certificate(aik_cert, &ceremony.aaguid)?;
+    aik_cert
+        .tbs_certificate
+        .subject
+        .0
+        .is_empty()
+        .then_some(())?;
+    extension(aik_cert, ID_CE_SUBJECT_ALT_NAME)?;
+    ExtendedKeyUsage::from_der(
+        extension(aik_cert, ID_CE_EXT_KEY_USAGE)?
+            .extn_value
+            .as_bytes(),
+    )
+    .ok()?
+    .0
+    .contains(&TCG_KP_AIK_CERTIFICATE)
+    .then_some(())?;
+
+    Some((AttestationType::AttCa, trust_path))
+}
+
+const TPM_ALG_RSA: u16 = 0x0001;
+const TPM_ALG_SHA256: u16 = 0x000b;
+const TPM_ALG_SHA384: u16 = 0x000c;
+const TPM_ALG_NULL: u16 = 0x0010;
+const TPM_ALG_ECC: u16 = 0x0023;
+const TPM_ECC_NIST_P256: u16 = 0x0003;
+
+/// Parses a `TPMT_PUBLIC` structure, returning its `nameAlg` and public key.
+fn parse_pub_area(pub_area: &[u8]) -> Option<(u16, CredentialPublicKey)> {
+    let mut reader = TpmReader(pub_area);
+    let key_type = reader.u16()?;
+    let name_alg = reader.u16()?;
+    // objectAttributes
+    reader.u32()?;
+    // authPolicy
+    reader.sized()?;
+
+    // symmetric
+    if reader.u16()? != TPM_ALG_NULL {
+        reader.take(4)?;
+    }
+    // scheme
+    if reader.u16()? != TPM_ALG_NULL {
+        reader.u16()?;
+    }
+
+    let key = match key_type {
+        TPM_ALG_RSA => {
+            // keyBits
+            reader.u16()?;
+            let exponent = match reader.u32()? {
+                0 => 65537,
+                exponent => exponent,
+            };
+            CredentialPublicKey::rs256(reader.sized()?, &exponent.to_be_bytes())?
+        }
+        TPM_ALG_ECC => {
+            (reader.u16()? == TPM_ECC_NIST_P256).then_some(())?;
+            // kdf
+            if reader.u16()? != TPM_ALG_NULL {
+                reader.u16()?;
+            }
+            CredentialPublicKey::es256(reader.sized()?, reader.sized()?)?
+        }
+        _ => return None,
+    };
+    reader.0.is_empty().then_some((name_alg, key))
+}
+
+/// Reads big-endian TPM structures.
+struct TpmReader<'a>(&'a [u8]);
+
+impl<'a> TpmReader<'a> {
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        let (taken, rest) = self.0.split_at_checked(n)?;
+        self.0 = rest;
+        Some(taken)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.take(2)?.try_into().ok().map(u16::from_be_bytes)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take(4)?.try_into().ok().map(u32::from_be_bytes)
+    }
+
+    /// A `TPM2B_*` structure: a `u16` size followed by as many bytes.
+    fn sized(&mut self) -> Option<&'a [u8]> {
+        let size = self.u16()?;
+        self.take(size as usize)
+    }
+}
+
+const INTEGER: u8 = 0x02;
+const OCTET_STRING: u8 = 0x04;
+const SEQUENCE: u8 = 0x30;
+const SET: u8 = 0x31;
+
+/// Reads DER elements one by one. Unlike `der`, it accepts the high tag numbers in Android's
+/// `AuthorizationList`.
+struct DerReader<'a>(&'a [u8]);
+
+impl<'a> DerReader<'a> {
+    /// Reads the next element, returning its tag number and contents.
+    fn next(&mut self) -> Option<(u32, DerReader<'a>)> {
+        let mut reader = TpmReader(self.0);
+        let identifier = reader.take(1)?[0];
+        let mut tag = (identifier & 0x1f) as u32;
+        if tag == 0x1f {
+            tag = 0;
+            loop {
+                let byte = reader.take(1)?[0];
+                tag = tag.checked_mul(128)? | (byte & 0x7f) as u32;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+            }
+        }
+        let len = match reader.take(1)?[0] {
+            len @ 0..=0x7f => len as usize,
+            0x81 => reader.take(1)?[0] as usize,
+            0x82 => reader.u16()? as usize,
+            _ => return None,
+        };
+        let contents = reader.take(len)?;
+        self.0 = reader.0;
+        Some((tag, DerReader(contents)))
+    }
+
+    /// Reads the next element, which must have the given identifier octet.
+    fn expect(&mut self, identifier: u8) -> Option<DerReader<'a>> {
+        (*self.0.first()? == identifier).then_some(())?;
+        self.next().map(|(_, contents)| contents)
+    }
+
+    /// Interprets the contents as a small, non-negative `INTEGER`.
+    fn uint(&self) -> Option<u64> {
+        (self.0.len() <= 8 && self.0.first()? & 0x80 == 0).then_some(())?;
+        Some(self.0.iter().fold(0, |n, byte| n << 8 | *byte as u64))
+    }
+}
+
+/// Verifies that each certificate of the trust path is issued by the next one, returning
+/// whether the last one is (or is issued by) a trust anchor.
+///
+/// Every issuer, trust anchors included, must be a CA allowed to sign certificates, whose
+/// `pathLenConstraint` (if any) admits the intermediate CAs below it. If `now` is given, every
+/// certificate of the trust path must be valid at it.
+pub(crate) fn verify_chain(
+    trust_path: &[Certificate],
+    trust_anchors: &[Certificate],
+    now: Option<u64>,
+) -> Option<bool> {
+    if let Some(now) = now {
+        trust_path
+            .iter()
+            .all(|cert| is_valid_at(cert, now))
+            .then_some(())?;
+    }
+    // The issuer of the `i`-th certificate has the `i - 1` intermediate CAs after the
+    // attestation certificate below it.
+    for (intermediates, pair) in trust_path.windows(2).enumerate() {
+        check_issuer(&pair[1], intermediates)?;
+        verify_issued_by(&pair[0], &pair[1])?;
+    }
+    let last = trust_path.last()?;
+    Some(trust_anchors.iter().any(|anchor| {
+        anchor == last
+            || (check_issuer(anchor, trust_path.len() - 1).is_some()
+                && verify_issued_by(last, anchor).is_some())
+    }))
+}
+
+/// Checks that `issuer` is a CA that may sign certificates, with `intermediates` CA
+/// certificates between it and the attestation certificate.
+fn check_issuer(issuer: &Certificate, intermediates: usize) -> Option<()> {
+    let ext = extension(issuer, ID_CE_BASIC_CONSTRAINTS)?;
+    let basic_constraints = BasicConstraints::from_der(ext.extn_value.as_bytes()).ok()?;
+    basic_constraints.ca.then_some(())?;
+    if let Some(path_len) = basic_constraints.path_len_constraint {
+        (intermediates <= path_len as usize).then_some(())?;
+    }
+    if let Some(ext) = extension(issuer, ID_CE_KEY_USAGE) {
+        let key_usage = KeyUsage::from_der(ext.extn_value.as_bytes()).ok()?;
+        key_usage.key_cert_sign().then_some(())?;
+    }
+    Some(())
+}
+
+/// Whether `now` (in seconds since the Unix epoch) is within the validity period of `cert`.
+fn is_valid_at(cert: &Certificate, now: u64) -> bool {
+    let validity = &cert.tbs_certificate.validity;
+    validity.not_before.to_unix_duration().as_secs() <= now
+        && now <= validity.not_after.to_unix_duration().as_secs()
+}
+
+fn verify_issued_by(cert: &Certificate, issuer: &Certificate) -> Option<()> {
+    (cert.tbs_certificate.issuer == issuer.tbs_certificate.subject).then_some(())?;
+    let tbs_certificate = cert.tbs_certificate.to_der().ok()?;
+    let signature = cert.signature.as_bytes()?;
+    let issuer_key = public_key_der(issuer)?;
+
+    match cert.signature_algorithm.oid {
+        ECDSA_WITH_SHA256 => {
+            verify_ecdsa_prehash(&issuer_key, &Sha256::digest(&tbs_certificate), signature)
+        }
+        ECDSA_WITH_SHA384 => {
+            verify_ecdsa_prehash(&issuer_key, &Sha384::digest(&tbs_certificate), signature)
+        }
+        SHA256_WITH_RSA_ENCRYPTION => {
+            verify_signature(Algorithm::Rs256, &tbs_certificate, signature, &issuer_key).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Verifies an ECDSA signature made with a P-256 or P-384 key, which certificate authorities
+/// may use with either SHA-256 or SHA-384.
+fn verify_ecdsa_prehash(public_key_der: &[u8], prehash: &[u8], signature: &[u8]) -> Option<()> {
+    use p256::{ecdsa::signature::hazmat::PrehashVerifier, pkcs8::DecodePublicKey};
+
+    if let Ok(key) = p256::ecdsa::VerifyingKey::from_public_key_der(public_key_der) {
+        let signature = p256::ecdsa::Signature::from_der(signature).ok()?;
+        return key.verify_prehash(prehash, &signature).ok();
+    }
+    let key = p384::ecdsa::VerifyingKey::from_public_key_der(public_key_der).ok()?;
+    let signature = p384::ecdsa::Signature::from_der(signature).ok()?;
+    key.verify_prehash(prehash, &signature).ok()
+}
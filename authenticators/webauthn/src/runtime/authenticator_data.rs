@@ -1,5 +1,6 @@
 extern crate alloc;
 
+use crate::Aaguid;
 use byteorder::{BigEndian, ByteOrder};
 use core::convert::TryFrom;
 use coset::{CborSerializable, CoseKey};
@@ -32,11 +33,8 @@ bitflags::bitflags! {
 
 #[derive(Debug)]
 pub struct AttestedCredentialData<'a> {
-    #[allow(dead_code)]
-    pub aaguid: [u8; 16],
-    #[allow(dead_code)]
+    pub aaguid: Aaguid,
     pub credential_id: &'a [u8],
-    #[allow(dead_code)]
     pub credential_public_key: CoseKey,
 }
 
@@ -45,6 +43,10 @@ pub struct AttestedCredentialData<'a> {
 #[derive(Debug)]
 pub enum ParseError {
     Truncated,
+    /// The CBOR is not well-formed, or nests too deep.
+    MalformedCbor,
+    /// There are bytes left after the last field.
+    TrailingData,
     Cose(coset::CoseError),
 }
 
@@ -53,6 +55,8 @@ impl core::fmt::Display for ParseError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             ParseError::Truncated => f.write_str("byte slice too short"),
+            ParseError::MalformedCbor => f.write_str("malformed CBOR"),
+            ParseError::TrailingData => f.write_str("trailing bytes"),
             ParseError::Cose(e) => write!(f, "CBOR error: {e}"),
         }
     }
@@ -69,51 +73,36 @@ impl<'a> TryFrom<&'a [u8]> for AuthenticatorData<'a> {
         if bytes.len() < 37 {
             return Err(ParseError::Truncated);
         }
-
-        let mut offset = 0;
-        let mut take = |n: usize| -> Result<&[u8], ParseError> {
-            bytes
-                .get(offset..offset + n)
-                .ok_or(ParseError::Truncated)
-                .inspect(|_| offset += n)
-        };
+        let mut reader = Reader(bytes);
 
         // Get `rpIdHash`
         let rp_id_hash = {
             let mut arr = [0u8; 32];
-            arr.copy_from_slice(take(32)?);
+            arr.copy_from_slice(reader.take(32)?);
             arr
         };
 
         // Get authenticator flags
-        let flags = AuthenticatorFlags::from_bits_truncate(take(1)?[0]);
+        let flags = AuthenticatorFlags::from_bits_truncate(reader.take(1)?[0]);
 
         // Get sign count
-        let sign_count = BigEndian::read_u32(take(4)?);
+        let sign_count = BigEndian::read_u32(reader.take(4)?);
 
         // Parse optional attestedCredentialData, if present.
         let attested_credential_data = if flags.contains(AuthenticatorFlags::AT) {
             let aaguid = {
                 let mut arr = [0u8; 16];
-                arr.copy_from_slice(take(16)?);
+                arr.copy_from_slice(reader.take(16)?);
                 arr
             };
 
-            let cred_id_len = BigEndian::read_u16(take(2)?) as usize;
-            let credential_id = take(cred_id_len)?;
-
-            // credentialPublicKey is CBOR – we don't know its length a-priori.
-            // Feed the *remaining* slice to serde_cbor...
-            let credential_public_key: CoseKey =
-                CoseKey::from_slice(&bytes[offset..]).map_err(ParseError::Cose)?;
+            let cred_id_len = BigEndian::read_u16(reader.take(2)?) as usize;
+            let credential_id = reader.take(cred_id_len)?;
 
-            // ...then ask how many bytes were read.
-            let len = credential_public_key
-                .clone()
-                .to_vec()
-                .map_err(ParseError::Cose)?
-                .len();
-            offset += len;
+            // credentialPublicKey is CBOR – we don't know its length a-priori, so read it
+            // off the item headers, letting the extensions, if any, follow it.
+            let credential_public_key =
+                CoseKey::from_slice(reader.take_cbor()?).map_err(ParseError::Cose)?;
 
             Some(AttestedCredentialData {
                 aaguid,
@@ -125,11 +114,15 @@ impl<'a> TryFrom<&'a [u8]> for AuthenticatorData<'a> {
         };
 
         let extensions = if flags.contains(AuthenticatorFlags::ED) {
-            Some(coset::cbor::Value::from_slice(&bytes[offset..]).map_err(ParseError::Cose)?)
+            Some(coset::cbor::Value::from_slice(reader.take_cbor()?).map_err(ParseError::Cose)?)
         } else {
             None
         };
 
+        if !reader.0.is_empty() {
+            return Err(ParseError::TrailingData);
+        }
+
         Ok(AuthenticatorData {
             rp_id_hash,
             flags,
@@ -139,3 +132,99 @@ impl<'a> TryFrom<&'a [u8]> for AuthenticatorData<'a> {
         })
     }
 }
+
+/// The bytes of the authenticator data yet to be parsed.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], ParseError> {
+        if self.0.len() < n {
+            return Err(ParseError::Truncated);
+        }
+        let (taken, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    /// Takes a single CBOR data item.
+    fn take_cbor(&mut self) -> Result<&'a [u8], ParseError> {
+        let mut len = 0;
+        skip_cbor_item(self.0, &mut len, MAX_CBOR_DEPTH)?;
+        self.take(len)
+    }
+}
+
+/// How deep CBOR items may nest, well beyond what keys and extension outputs need. It bounds
+/// the recursion on untrusted input.
+const MAX_CBOR_DEPTH: usize = 16;
+
+/// Advances `offset` past the CBOR data item it points at, only reading item headers.
+///
+/// Unlike re-encoding the decoded item, this measures the actual encoding, which might not be
+/// canonical (e.g. non-minimal lengths, or indefinite-length items).
+fn skip_cbor_item(bytes: &[u8], offset: &mut usize, depth: usize) -> Result<(), ParseError> {
+    let depth = depth.checked_sub(1).ok_or(ParseError::MalformedCbor)?;
+
+    let initial_byte = *bytes.get(*offset).ok_or(ParseError::Truncated)?;
+    *offset += 1;
+    let (major_type, additional_info) = (initial_byte >> 5, initial_byte & 0x1f);
+    let argument = match additional_info {
+        0..=23 => Some(additional_info as u64),
+        24..=27 => {
+            let len = 1 << (additional_info - 24);
+            let argument = bytes
+                .get(*offset..*offset + len)
+                .ok_or(ParseError::Truncated)?;
+            *offset += len;
+            Some(BigEndian::read_uint(argument, len))
+        }
+        // Indefinite length
+        31 => None,
+        _ => return Err(ParseError::MalformedCbor),
+    };
+
+    match (major_type, argument) {
+        // Integers, simple values and floats
+        (0 | 1 | 7, Some(_)) => Ok(()),
+        // Byte and text strings
+        (2 | 3, Some(len)) => {
+            let end = usize::try_from(len)
+                .ok()
+                .and_then(|len| offset.checked_add(len))
+                .filter(|end| *end <= bytes.len())
+                .ok_or(ParseError::Truncated)?;
+            *offset = end;
+            Ok(())
+        }
+        // Arrays and maps. Every item takes at least a byte, so huge lengths get truncated
+        // as soon as the bytes run out.
+        (4 | 5, Some(len)) => {
+            let items = if major_type == 5 {
+                len.saturating_mul(2)
+            } else {
+                len
+            };
+            (0..items).try_for_each(|_| skip_cbor_item(bytes, offset, depth))
+        }
+        // Tags
+        (6, Some(_)) => skip_cbor_item(bytes, offset, depth),
+        // Indefinite-length strings, arrays and maps, up to the "break" stop code
+        (2..=5, None) => {
+            while *bytes.get(*offset).ok_or(ParseError::Truncated)? != BREAK {
+                // Strings are made of definite-length chunks of the same type
+                let chunk = bytes[*offset];
+                if major_type <= 3 && (chunk >> 5 != major_type || chunk & 0x1f == 31) {
+                    return Err(ParseError::MalformedCbor);
+                }
+                skip_cbor_item(bytes, offset, depth)?;
+            }
+            *offset += 1;
+            Ok(())
+        }
+        // A "break" outside of an indefinite-length item
+        _ => Err(ParseError::MalformedCbor),
+    }
+}
+
+/// The stop code of indefinite-length items.
+const BREAK: u8 = 0xff;
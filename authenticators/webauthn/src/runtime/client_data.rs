@@ -10,11 +10,10 @@ use frame::prelude::*;
 pub struct RawClientData {
     r#type: String,
     challenge: String,
-    #[allow(dead_code)]
     pub origin: String,
     #[serde(default)]
-    #[allow(dead_code)]
     pub cross_origin: bool,
+    pub top_origin: Option<String>,
 }
 
 impl TryFrom<Vec<u8>> for RawClientData {
@@ -0,0 +1,266 @@
+use super::*;
+
+use alloc::{format, vec::Vec};
+use attestation_statement::VerifiedAttestation;
+use extensions::CredProtect;
+use url::Url;
+
+/// A DER-encoded X.509 certificate.
+pub type TrustAnchor = Vec<u8>;
+
+/// An origin allowed to act on behalf of an authority.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AllowedOrigin {
+    /// An `https` origin on exactly this host, e.g. `pass.example.com`.
+    Host(String),
+    /// An `https` origin on this domain or any of its subdomains.
+    Domain(String),
+    /// An Android app, identified by the unpadded base64url SHA-256 hash of its signing
+    /// certificate, as in its `android:apk-key-hash:` origin.
+    AndroidApp(String),
+}
+
+impl AllowedOrigin {
+    pub fn matches(&self, origin: &str) -> bool {
+        match self {
+            Self::Host(host) => https_host(origin).is_some_and(|h| h == *host),
+            Self::Domain(domain) => https_host(origin).is_some_and(|h| is_within(&h, domain)),
+            Self::AndroidApp(key_hash) => origin == format!("android:apk-key-hash:{key_hash}"),
+        }
+    }
+}
+
+/// How assertions whose signature counter doesn't increase are handled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignCountPolicy {
+    /// Reject them, as the credential might have been cloned. Nothing recorded about them
+    /// outlives the rejection.
+    Reject,
+    /// Accept them, only reporting them to [`Config::on_possible_clone`].
+    Report,
+    /// Don't check signature counters at all.
+    Ignore,
+}
+
+/// Which backup eligibility (BE) and backup state (BS) combinations credentials may have.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackupStatePolicy {
+    /// Accept any, only reporting changes of backup state to
+    /// [`Config::on_backup_state_change`].
+    Any,
+    /// Reject assertions of credentials that weren't backed up on registration, but are now.
+    NeverBackedUpAfterRegistration,
+    /// Only accept single-device credentials, which aren't backup eligible.
+    SingleDevice,
+}
+
+/// Runtime-defined policies for the WebAuthn authenticator.
+///
+/// Every item but [`is_public_suffix`](Self::is_public_suffix) has a default, which is what
+/// `()` uses. Other than requiring user verification on registration, defaults are permissive.
+pub trait Config: Clone + Eq + core::fmt::Debug + 'static {
+    /// Root certificates that attestation certificate chains are checked against. An
+    /// attestation is only [trusted](VerifiedAttestation::trusted) if its chain leads to one
+    /// of these.
+    fn trust_anchors() -> Vec<TrustAnchor> {
+        Vec::new()
+    }
+
+    /// The current time, in seconds since the Unix epoch, which every certificate of an
+    /// attestation chain must be valid at. `None` doesn't check validity periods.
+    fn unix_time() -> Option<u64> {
+        None
+    }
+
+    /// Decides whether a device can be registered with the given (already verified)
+    /// attestation statement.
+    ///
+    /// Runtimes that only want hardware-backed passkeys can require
+    /// [`VerifiedAttestation::is_hardware_backed`].
+    fn allow_attestation(_attestation: &VerifiedAttestation) -> bool {
+        true
+    }
+
+    /// The model of authenticators with `aaguid`, if known. Runtimes can look it up in an
+    /// allowlist built out of the FIDO Metadata Service with `mds-allowlist`.
+    fn authenticator_model(_aaguid: &Aaguid) -> Option<AuthenticatorModel> {
+        None
+    }
+
+    /// Decides whether passkeys created on authenticators with `aaguid` can be registered,
+    /// given their [model](Self::authenticator_model) if known. By default, any authenticator
+    /// is allowed unless its model is revoked.
+    ///
+    /// AAGUIDs are only as trustworthy as the attestation that signs them, so runtimes
+    /// relying on them should also require [trusted](VerifiedAttestation::trusted)
+    /// attestations.
+    fn allow_authenticator(_aaguid: &Aaguid, model: Option<&AuthenticatorModel>) -> bool {
+        model.is_none_or(|model| !model.revoked)
+    }
+
+    /// Whether registering a passkey requires user verification (e.g. a biometric or PIN).
+    fn registration_user_verification() -> UserVerificationRequirement {
+        UserVerificationRequirement::Required
+    }
+
+    /// Whether authenticating with a passkey requires user verification. Otherwise, user
+    /// presence (e.g. touching a security key) is enough.
+    fn authentication_user_verification() -> UserVerificationRequirement {
+        UserVerificationRequirement::Preferred
+    }
+
+    /// How signature counters are checked. Counters that stay at 0, as with synced passkeys,
+    /// are never considered a clone.
+    fn sign_count_policy() -> SignCountPolicy {
+        SignCountPolicy::Reject
+    }
+
+    /// Called when a genuine assertion of `device_id` doesn't increase its signature counter,
+    /// unless the policy is [`SignCountPolicy::Ignore`].
+    ///
+    /// Assertions are verified while validating transactions too, where storage changes are
+    /// discarded, so it might be called more than once for the same assertion.
+    ///
+    /// Under [`SignCountPolicy::Reject`] the call verifying the assertion fails, rolling back
+    /// whatever this writes to storage, events included. Runtimes that need to keep track of
+    /// possible clones have to [`Report`](SignCountPolicy::Report) them instead, e.g. flagging
+    /// the device so it can be revoked.
+    fn on_possible_clone(_device_id: &DeviceId, _stored: u32, _received: u32) {}
+
+    /// Which backup eligibility and backup state combinations credentials may have.
+    fn backup_state_policy() -> BackupStatePolicy {
+        BackupStatePolicy::Any
+    }
+
+    /// Called when a genuine assertion of `device_id` changes its backup state, e.g. when a
+    /// device-bound passkey becomes synced (`backed_up`).
+    ///
+    /// As with [`on_possible_clone`](Self::on_possible_clone), it might be called more than
+    /// once for the same assertion.
+    fn on_backup_state_change(_device_id: &DeviceId, _backed_up: bool) {}
+
+    /// The minimum `credProtect` level credentials must be created with. `None` doesn't
+    /// require the extension.
+    fn min_cred_protect() -> Option<CredProtect> {
+        None
+    }
+
+    /// Identifiers of the extensions clients are expected to request. Ceremonies with outputs
+    /// for any other extension are rejected. `None` accepts any.
+    fn requested_extensions() -> Option<Vec<&'static str>> {
+        None
+    }
+
+    /// The origins allowed to register and use passkeys for `authority`. `None` allows any.
+    fn allowed_origins(_authority: &AuthorityId) -> Option<Vec<AllowedOrigin>> {
+        None
+    }
+
+    /// The top-level origins allowed to embed ceremonies for `authority` in a cross-origin
+    /// iframe. Cross-origin ceremonies are rejected unless their `topOrigin` is one of these,
+    /// so none are allowed by default.
+    fn allowed_top_origins(_authority: &AuthorityId) -> Vec<AllowedOrigin> {
+        Vec::new()
+    }
+
+    /// The RP ID passkeys for `authority` are scoped to. `None` takes the host of the calling
+    /// origin, so it must be set for authorities used from native apps.
+    fn rp_id(_authority: &AuthorityId) -> Option<String> {
+        None
+    }
+
+    /// The current block, which records keep as the one their credential was created or last
+    /// used at. Runtimes take it from `frame_system`, like
+    /// `frame_system::Pallet::<Runtime>::block_number().try_into().ok()`. `None` keeps neither.
+    fn block_number() -> Option<u32> {
+        None
+    }
+
+    /// Whether `domain` is a public suffix (like `com` or `co.uk`), which can't be an RP ID.
+    ///
+    /// Only the [Public Suffix List](https://publicsuffix.org) tells, so there's no default.
+    /// Runtimes that [set the RP ID](Self::rp_id) of every authority can accept just those.
+    fn is_public_suffix(domain: &str) -> bool;
+}
+
+impl Config for () {
+    /// Only top-level domains are, which lets through suffixes like `co.uk`. Runtimes should
+    /// use a `Config` of their own.
+    fn is_public_suffix(domain: &str) -> bool {
+        !domain.contains('.')
+    }
+}
+
+/// Checks that `origin` is allowed to act on behalf of `authority`, returning the hash of
+/// the RP ID it may claim.
+pub(crate) fn rp_id_hash<P: Config>(
+    authority: &AuthorityId,
+    origin: &str,
+) -> Result<[u8; 32], WebAuthnError> {
+    if let Some(allowed_origins) = P::allowed_origins(authority) {
+        allowed_origins
+            .iter()
+            .any(|allowed| allowed.matches(origin))
+            .then_some(())
+            .ok_or(WebAuthnError::OriginNotAllowed)?;
+    }
+
+    let host = Url::parse(origin)
+        .ok()
+        .and_then(|origin| origin.domain().map(String::from));
+    let rp_id = match (P::rp_id(authority), host) {
+        // Web origins may only claim their host or a registrable suffix of it.
+        (Some(rp_id), Some(host)) => {
+            is_within(&host, &rp_id)
+                .then_some(())
+                .ok_or(WebAuthnError::InvalidRpId)?;
+            rp_id
+        }
+        // Origins without a host, like native apps, may only claim it if explicitly allowed.
+        (Some(rp_id), None) => P::allowed_origins(authority)
+            .is_some()
+            .then_some(rp_id)
+            .ok_or(WebAuthnError::OriginNotAllowed)?,
+        (None, host) => host.ok_or(WebAuthnError::InvalidRpId)?,
+    };
+    if P::is_public_suffix(&rp_id) {
+        return Err(WebAuthnError::InvalidRpId);
+    }
+
+    Ok(frame::hashing::sha2_256(rp_id.as_bytes()))
+}
+
+/// Checks that a cross-origin ceremony for `authority` is embedded by an allowed top-level
+/// origin.
+pub(crate) fn check_cross_origin<P: Config>(
+    authority: &AuthorityId,
+    cross_origin: bool,
+    top_origin: Option<&str>,
+) -> Result<(), WebAuthnError> {
+    // Clients only set `topOrigin` for cross-origin ceremonies, but don't rely on it.
+    if !cross_origin && top_origin.is_none() {
+        return Ok(());
+    }
+    top_origin
+        .is_some_and(|top_origin| {
+            P::allowed_top_origins(authority)
+                .iter()
+                .any(|allowed| allowed.matches(top_origin))
+        })
+        .then_some(())
+        .ok_or(WebAuthnError::CrossOriginNotAllowed)
+}
+
+fn https_host(origin: &str) -> Option<String> {
+    let origin = Url::parse(origin).ok()?;
+    (origin.scheme() == "https").then_some(())?;
+    origin.domain().map(String::from)
+}
+
+/// Whether `host` is `domain` or one of its subdomains.
+fn is_within(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|subdomain| subdomain.ends_with('.'))
+}
@@ -0,0 +1,129 @@
+use super::*;
+
+use alloc::vec::Vec;
+use coset::{
+    iana::{self, EnumI64},
+    CoseKey, KeyType, Label, RegisteredLabelWithPrivate,
+};
+
+const P256_SPKI_PREFIX: [u8; 27] = [
+    0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a,
+    0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00, 0x04,
+];
+const ED25519_SPKI_PREFIX: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+/// `AlgorithmIdentifier { rsaEncryption, NULL }`
+const RSA_ALGORITHM_IDENTIFIER: [u8; 15] = [
+    0x30, 0x0d, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01, 0x05, 0x00,
+];
+
+impl CredentialPublicKey {
+    /// A P-256 key, from its affine coordinates.
+    pub fn es256(x: &[u8], y: &[u8]) -> Option<Self> {
+        if x.len() != 32 || y.len() != 32 {
+            return None;
+        }
+        let mut der = [0u8; 91];
+        der[..27].copy_from_slice(&P256_SPKI_PREFIX);
+        der[27..59].copy_from_slice(x);
+        der[59..].copy_from_slice(y);
+        Some(Self::Es256(der))
+    }
+
+    /// An Ed25519 key, from its 32-byte encoding.
+    pub fn eddsa(x: &[u8]) -> Option<Self> {
+        if x.len() != 32 {
+            return None;
+        }
+        let mut der = [0u8; 44];
+        der[..12].copy_from_slice(&ED25519_SPKI_PREFIX);
+        der[12..].copy_from_slice(x);
+        Some(Self::EdDsa(der))
+    }
+
+    /// An RSA key, from its big-endian modulus and public exponent.
+    pub fn rs256(n: &[u8], e: &[u8]) -> Option<Self> {
+        let rsa_public_key = der_tlv(
+            0x30,
+            &[der_unsigned_integer(n), der_unsigned_integer(e)].concat(),
+        );
+        let subject_public_key = der_tlv(0x03, &[&[0x00], rsa_public_key.as_slice()].concat());
+        let der = der_tlv(
+            0x30,
+            &[RSA_ALGORITHM_IDENTIFIER.as_slice(), &subject_public_key].concat(),
+        );
+        der.try_into().ok().map(Self::Rs256)
+    }
+}
+
+impl TryFrom<&CoseKey> for CredentialPublicKey {
+    type Error = ();
+
+    fn try_from(key: &CoseKey) -> Result<Self, Self::Error> {
+        let param = |label: i64| {
+            key.params.iter().find_map(|(l, value)| match l {
+                Label::Int(l) if *l == label => value.as_bytes().map(Vec::as_slice),
+                _ => None,
+            })
+        };
+        let curve = key.params.iter().find_map(|(l, value)| match l {
+            Label::Int(-1) => value.as_integer().and_then(|c| i64::try_from(c).ok()),
+            _ => None,
+        });
+
+        match (&key.kty, key_algorithm(key)?) {
+            (KeyType::Assigned(iana::KeyType::EC2), Some(Algorithm::Es256) | None)
+                if curve == Some(iana::EllipticCurve::P_256.to_i64()) =>
+            {
+                Self::es256(param(-2).ok_or(())?, param(-3).ok_or(())?)
+            }
+            (KeyType::Assigned(iana::KeyType::OKP), Some(Algorithm::EdDsa) | None)
+                if curve == Some(iana::EllipticCurve::Ed25519.to_i64()) =>
+            {
+                Self::eddsa(param(-2).ok_or(())?)
+            }
+            (KeyType::Assigned(iana::KeyType::RSA), Some(Algorithm::Rs256) | None) => {
+                Self::rs256(param(-1).ok_or(())?, param(-2).ok_or(())?)
+            }
+            _ => None,
+        }
+        .ok_or(())
+    }
+}
+
+/// The algorithm a COSE key is restricted to, if any. Fails if it's not supported.
+pub(crate) fn key_algorithm(key: &CoseKey) -> Result<Option<Algorithm>, ()> {
+    match &key.alg {
+        None => Ok(None),
+        Some(RegisteredLabelWithPrivate::Assigned(alg)) => {
+            Algorithm::from_cose(alg.to_i64()).map(Some).ok_or(())
+        }
+        Some(_) => Err(()),
+    }
+}
+
+fn der_tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut tlv = Vec::with_capacity(content.len() + 4);
+    tlv.push(tag);
+    match content.len() {
+        len @ 0..=0x7f => tlv.push(len as u8),
+        len @ 0x80..=0xff => tlv.extend_from_slice(&[0x81, len as u8]),
+        len => {
+            tlv.push(0x82);
+            tlv.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+    }
+    tlv.extend_from_slice(content);
+    tlv
+}
+
+fn der_unsigned_integer(bytes: &[u8]) -> Vec<u8> {
+    let first = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    let bytes = &bytes[first..];
+    match bytes.first() {
+        None => der_tlv(0x02, &[0x00]),
+        Some(b) if b & 0x80 != 0 => der_tlv(0x02, &[&[0x00], bytes].concat()),
+        Some(_) => der_tlv(0x02, bytes),
+    }
+}
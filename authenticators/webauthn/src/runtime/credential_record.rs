@@ -3,23 +3,40 @@ use frame::deps::sp_core::hexdisplay::AsBytesRef;
 
 use crate::runtime::authenticator_data::AuthenticatorFlags;
 use authenticator_data::AuthenticatorData;
+use config::{BackupStatePolicy, SignCountPolicy};
 use traits_authn::{util::VerifyCredential, Challenger};
-use webauthn_verifier::webauthn_verify as verify_signature;
+use webauthn_verifier::webauthn_verify_prepared;
 
-const LOG_TARGET: &str = "pass::webauthn";
-
-impl<Ch, A> From<Attestation<CxOf<Ch>>> for Device<Ch, A>
+impl<Ch, A, P> From<Attestation<CxOf<Ch>, P>> for Device<Ch, A, P>
 where
     Ch: Challenger,
     CxOf<Ch>: Parameter + Copy + 'static,
+    P: Config,
 {
-    fn from(value: Attestation<CxOf<Ch>>) -> Self {
+    fn from(value: Attestation<CxOf<Ch>, P>) -> Self {
+        let authenticator_data = value.authenticator_data().unwrap_or_default();
         if let Ok(authenticator_data) =
-            TryInto::<AuthenticatorData>::try_into(value.authenticator_data.as_bytes_ref())
+            TryInto::<AuthenticatorData>::try_into(authenticator_data.as_slice())
         {
+            // Valid attestations carry the attested key in `public_key` too, but prefer the one
+            // that's been signed over.
+            let public_key = authenticator_data
+                .attested_credential_data
+                .as_ref()
+                .and_then(|att_credential| (&att_credential.credential_public_key).try_into().ok())
+                .unwrap_or(value.public_key);
+            let aaguid = authenticator_data
+                .attested_credential_data
+                .as_ref()
+                .map(|att_credential| att_credential.aaguid);
+            let credential_id = authenticator_data
+                .attested_credential_data
+                .as_ref()
+                .and_then(|att_credential| att_credential.credential_id.to_vec().try_into().ok());
+
             Device::new(CredentialRecord {
                 device_id: *value.device_id(),
-                public_key: value.public_key,
+                public_key,
                 sign_count: 0,
                 backup_eligible: authenticator_data
                     .flags
@@ -27,6 +44,16 @@ where
                 backup_state: authenticator_data
                     .flags
                     .contains(AuthenticatorFlags::BACKUP_STATE),
+                version: CREDENTIAL_RECORD_VERSION,
+                rp_id_hash: Some(authenticator_data.rp_id_hash),
+                reported_resident_key: value.client_extensions.resident_key,
+                aaguid,
+                model: aaguid.as_ref().and_then(P::authenticator_model),
+                credential_id,
+                transports: value.transports,
+                created_at: P::block_number(),
+                last_used_at: None,
+                user_handle: Some(value.user_handle),
             })
         } else {
             // Creation of the `CredentialRecord` must be infallible to comply
@@ -37,22 +64,65 @@ where
                 sign_count: 0,
                 backup_eligible: false,
                 backup_state: false,
+                version: CREDENTIAL_RECORD_VERSION,
+                rp_id_hash: None,
+                reported_resident_key: value.client_extensions.resident_key,
+                aaguid: None,
+                model: None,
+                credential_id: None,
+                transports: value.transports,
+                created_at: P::block_number(),
+                last_used_at: None,
+                user_handle: Some(value.user_handle),
             })
         }
     }
 }
 
-impl<Cx> VerifyCredential<Assertion<Cx>> for CredentialRecord {
-    fn verify(&mut self, credential: &Assertion<Cx>) -> Option<()> {
-        let Ok(authenticator_data): Result<AuthenticatorData, _> =
-            credential.authenticator_data.as_bytes_ref().try_into()
-        else {
-            log::trace!(
-                target: LOG_TARGET,
-                "Failed to parse credential's authenticator_data"
-            );
-            return None;
-        };
+impl<Cx, P: Config> VerifyCredential<Assertion<Cx, P>> for CredentialRecord {
+    fn verify(&mut self, credential: &Assertion<Cx, P>) -> Option<()> {
+        self.verify_assertion(credential)
+            .inspect_err(|e| log::debug!(target: LOG_TARGET, "Invalid credential: {e:?}"))
+            .ok()
+    }
+}
+
+/// What the signature counter of an assertion says about the authenticator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignCountCheck {
+    /// The authenticator doesn't implement a counter (both are 0), like synced passkeys.
+    Unsupported,
+    /// The counter increased, as expected.
+    Increased,
+    /// The counter didn't increase, so the credential might have been cloned.
+    PossibleClone { stored: u32, received: u32 },
+}
+
+impl CredentialRecord {
+    /// Verifies that `credential` is signed by this credential, updating its signature
+    /// counter, backup state and the block it was last used at.
+    pub fn verify_assertion<Cx, P: Config>(
+        &mut self,
+        credential: &Assertion<Cx, P>,
+    ) -> Result<(), WebAuthnError> {
+        let public_key = self
+            .prepared_key()
+            .map_err(|_| WebAuthnError::InvalidSignature)?;
+        self.verify_assertion_with_key(credential, &public_key)
+    }
+
+    /// Like [`verify_assertion`](Self::verify_assertion), with the already
+    /// [prepared](Self::prepared_key) key of this credential.
+    pub fn verify_assertion_with_key<Cx, P: Config>(
+        &mut self,
+        credential: &Assertion<Cx, P>,
+        public_key: &PreparedKey,
+    ) -> Result<(), WebAuthnError> {
+        let authenticator_data: AuthenticatorData = credential
+            .authenticator_data
+            .as_bytes_ref()
+            .try_into()
+            .map_err(|_| WebAuthnError::InvalidAuthenticatorData)?;
 
         log::trace!(
             target: LOG_TARGET,
@@ -61,31 +131,97 @@ impl<Cx> VerifyCredential<Assertion<Cx>> for CredentialRecord {
             &credential.signature
         );
 
-        // Verify sign count
-        ((authenticator_data.sign_count == 0 || authenticator_data.sign_count > self.sign_count)
-            // Check BE
-            && (self.backup_eligible
-                == authenticator_data
-                    .flags
-                    .contains(AuthenticatorFlags::BACKUP_ELEGIBILITY)))
-        .then_some(())
-        .and_then(|_| {
-            verify_signature(
-                &credential.authenticator_data,
-                &credential.client_data,
-                &credential.signature,
-                &self.public_key,
-            )
-            .ok()?;
-
-            // Update `signCount` and BS.
-            self.sign_count = authenticator_data.sign_count;
-            self.backup_state = authenticator_data
+        // The userHandle, if any, is the user.id the credential was created with. Legacy
+        // records don't know it, so it must be the user the assertion is for.
+        if credential.user_handle.is_some_and(|user_handle| {
+            user_handle != self.user_handle.unwrap_or(credential.meta.user_id)
+        }) {
+            return Err(WebAuthnError::UserHandleMismatch);
+        }
+        // The credential is used for the RP it was registered with
+        if self
+            .rp_id_hash
+            .is_some_and(|rp_id_hash| rp_id_hash != authenticator_data.rp_id_hash)
+        {
+            return Err(WebAuthnError::RpIdMismatch);
+        }
+        // Check BE
+        if self.backup_eligible
+            != authenticator_data
                 .flags
-                .contains(AuthenticatorFlags::BACKUP_STATE);
+                .contains(AuthenticatorFlags::BACKUP_ELEGIBILITY)
+        {
+            return Err(WebAuthnError::BackupEligibilityMismatch);
+        }
+        // Check BE and BS against the backup state policy
+        let backup_state = authenticator_data
+            .flags
+            .contains(AuthenticatorFlags::BACKUP_STATE);
+        match P::backup_state_policy() {
+            BackupStatePolicy::SingleDevice if self.backup_eligible => {
+                return Err(WebAuthnError::BackupEligibleNotAllowed);
+            }
+            BackupStatePolicy::NeverBackedUpAfterRegistration
+                if backup_state && !self.backup_state =>
+            {
+                return Err(WebAuthnError::BackedUpAfterRegistration);
+            }
+            _ => {}
+        }
 
-            Some(())
-        })
+        webauthn_verify_prepared(
+            public_key,
+            &credential.authenticator_data,
+            &credential.client_data,
+            &credential.signature,
+        )
+        .map_err(|_| WebAuthnError::InvalidSignature)?;
+
+        // Verify sign count, only once the signature proves the assertion is genuine.
+        match (
+            P::sign_count_policy(),
+            self.check_sign_count(authenticator_data.sign_count),
+        ) {
+            (SignCountPolicy::Ignore, _) | (_, SignCountCheck::Unsupported) => {}
+            (_, SignCountCheck::Increased) => self.sign_count = authenticator_data.sign_count,
+            (policy, SignCountCheck::PossibleClone { stored, received }) => {
+                log::warn!(
+                    target: LOG_TARGET,
+                    "Possible clone of device {:?}: sign count went from {stored} to {received}",
+                    &self.device_id
+                );
+                P::on_possible_clone(&self.device_id, stored, received);
+                if policy == SignCountPolicy::Reject {
+                    return Err(WebAuthnError::PossibleClone);
+                }
+            }
+        }
+
+        // Update BS.
+        if backup_state != self.backup_state {
+            log::info!(
+                target: LOG_TARGET,
+                "Backup state of device {:?} went from {} to {backup_state}",
+                &self.device_id,
+                self.backup_state
+            );
+            P::on_backup_state_change(&self.device_id, backup_state);
+        }
+        self.backup_state = backup_state;
+        if let Some(block) = P::block_number() {
+            self.last_used_at = Some(block);
+        }
+
+        Ok(())
+    }
+
+    /// Compares the `received` signature counter of an assertion with the stored one.
+    pub fn check_sign_count(&self, received: u32) -> SignCountCheck {
+        match (self.sign_count, received) {
+            (0, 0) => SignCountCheck::Unsupported,
+            (stored, received) if received > stored => SignCountCheck::Increased,
+            (stored, received) => SignCountCheck::PossibleClone { stored, received },
+        }
     }
 }
 
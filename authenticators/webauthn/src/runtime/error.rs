@@ -0,0 +1,60 @@
+/// Why an [`Attestation`](crate::Attestation) or [`Assertion`](crate::Assertion) failed to
+/// verify.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebAuthnError {
+    /// `clientDataJSON` is not valid JSON, or misses required fields.
+    InvalidClientData,
+    /// `authenticatorData` is malformed.
+    InvalidAuthenticatorData,
+    /// `attestationObject` is malformed.
+    InvalidAttestationObject,
+    /// `clientData.type` is not the one of the ceremony.
+    WrongRequestType,
+    /// The origin is not allowed to act on behalf of the authority.
+    OriginNotAllowed,
+    /// The origin can't claim the configured RP ID, or the RP ID is a public suffix.
+    InvalidRpId,
+    /// The ceremony is cross-origin, and its `topOrigin` is not allowed to embed it.
+    CrossOriginNotAllowed,
+    /// `rpIdHash` is not the hash of the RP ID the origin claims, or the one the credential
+    /// was registered with.
+    RpIdMismatch,
+    /// The credential is backed up (BS) but not backup eligible (BE).
+    BackupStateWithoutEligibility,
+    /// Backup eligibility differs from the one the credential was registered with.
+    BackupEligibilityMismatch,
+    /// The runtime only accepts single-device credentials, and this one is backup eligible.
+    BackupEligibleNotAllowed,
+    /// The credential became backed up after registration, which the runtime doesn't allow.
+    BackedUpAfterRegistration,
+    /// The user was not present (UP).
+    UserNotPresent,
+    /// The user was not verified (UV), but the runtime requires it.
+    UserNotVerified,
+    /// The `userHandle` is not the user the assertion is for.
+    UserHandleMismatch,
+    /// The attestation carries no attested credential data.
+    MissingAttestedCredential,
+    /// The credential ID exceeds 1023 bytes.
+    CredentialIdTooLong,
+    /// The device ID is not the hash of the attested credential ID.
+    DeviceIdMismatch,
+    /// The public key is not the one attested in `authenticatorData`.
+    PublicKeyMismatch,
+    /// The extension outputs are malformed.
+    InvalidExtensions,
+    /// There's an output for an extension the runtime doesn't request.
+    UnrequestedExtension,
+    /// The credential is created with less `credProtect` than the runtime requires.
+    InsufficientCredProtect,
+    /// The attestation statement doesn't verify.
+    InvalidAttestationStatement,
+    /// The runtime doesn't allow the attestation statement.
+    AttestationNotAllowed,
+    /// The runtime doesn't allow the authenticator model.
+    AuthenticatorNotAllowed,
+    /// The signature doesn't verify against the credential public key.
+    InvalidSignature,
+    /// The signature counter didn't increase, so the credential might have been cloned.
+    PossibleClone,
+}
@@ -0,0 +1,99 @@
+//! Authenticator extension outputs, as found in `authenticatorData`. See
+//! [§9 WebAuthn Extensions](https://www.w3.org/TR/webauthn-3/#sctn-extensions).
+
+use super::*;
+
+use alloc::vec::Vec;
+use coset::cbor::Value;
+
+/// Protection level of a credential, set by the `credProtect` extension.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum CredProtect {
+    UserVerificationOptional = 1,
+    UserVerificationOptionalWithCredentialIdList = 2,
+    UserVerificationRequired = 3,
+}
+
+/// The extension outputs an authenticator signed over.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuthenticatorExtensionOutputs {
+    /// `credProtect`, on registration.
+    pub cred_protect: Option<CredProtect>,
+    /// `hmac-secret`, which backs `prf`, on registration.
+    pub hmac_secret: Option<bool>,
+    /// Identifiers of every extension with an output, including those not parsed above.
+    pub identifiers: Vec<String>,
+}
+
+impl TryFrom<&Value> for AuthenticatorExtensionOutputs {
+    type Error = ();
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let mut outputs = Self::default();
+        for (identifier, output) in value.as_map().ok_or(())? {
+            let identifier = identifier.as_text().ok_or(())?;
+            match identifier {
+                "credProtect" => {
+                    outputs.cred_protect = Some(
+                        match output
+                            .as_integer()
+                            .and_then(|level| u8::try_from(level).ok())
+                        {
+                            Some(1) => CredProtect::UserVerificationOptional,
+                            Some(2) => CredProtect::UserVerificationOptionalWithCredentialIdList,
+                            Some(3) => CredProtect::UserVerificationRequired,
+                            _ => return Err(()),
+                        },
+                    )
+                }
+                "hmac-secret" => outputs.hmac_secret = Some(output.as_bool().ok_or(())?),
+                _ => {}
+            }
+            outputs.identifiers.push(identifier.into());
+        }
+        Ok(outputs)
+    }
+}
+
+/// Checks the extension outputs of a ceremony against the policies of `P`.
+pub(crate) fn verify<P: Config>(
+    extensions: Option<&Value>,
+    registration: bool,
+) -> Result<(), WebAuthnError> {
+    let outputs: AuthenticatorExtensionOutputs = extensions
+        .map_or(Ok(Default::default()), TryInto::try_into)
+        .map_err(|_| WebAuthnError::InvalidExtensions)?;
+
+    // No outputs for extensions that weren't requested
+    if let Some(requested) = P::requested_extensions() {
+        outputs
+            .identifiers
+            .iter()
+            .all(|identifier| requested.contains(&identifier.as_str()))
+            .then_some(())
+            .ok_or(WebAuthnError::UnrequestedExtension)?;
+    }
+
+    // Credentials are created with enough protection
+    if registration
+        && P::min_cred_protect()
+            .is_some_and(|min| outputs.cred_protect.is_none_or(|level| level < min))
+    {
+        return Err(WebAuthnError::InsufficientCredProtect);
+    }
+
+    Ok(())
+}
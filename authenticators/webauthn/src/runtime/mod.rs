@@ -7,14 +7,33 @@ use traits_authn::{
     AuthorityId, Challenge, Challenger, DeviceChallengeResponse, DeviceId,
 };
 
+const LOG_TARGET: &str = "pass::webauthn";
+
 type CxOf<Ch> = <Ch as Challenger>::Context;
 
-pub type Authenticator<Ch, A> = Auth<Device<Ch, A>, Attestation<CxOf<Ch>>>;
-pub type Device<Ch, A> = Dev<CredentialRecord, A, Ch, Assertion<CxOf<Ch>>>;
+pub type Authenticator<Ch, A, P = ()> = Auth<Device<Ch, A, P>, Attestation<CxOf<Ch>, P>>;
+pub type Device<Ch, A, P = ()> = Dev<CredentialRecord, A, Ch, Assertion<CxOf<Ch>, P>>;
 
 pub mod assertion;
 pub mod attestation;
+pub mod attestation_statement;
+pub mod config;
 pub mod credential_record;
+pub mod error;
+pub mod extensions;
 
-mod authenticator_data;
+pub(crate) mod authenticator_data;
 mod client_data;
+mod credential_public_key;
+
+use config::Config;
+use error::WebAuthnError;
+
+/// The parsers of untrusted input, for fuzz targets to reach.
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing {
+    pub use super::{
+        authenticator_data::{AuthenticatorData, ParseError},
+        client_data::RawClientData,
+    };
+}
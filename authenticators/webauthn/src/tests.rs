@@ -10,6 +10,10 @@ parameter_types! {
 
 mod attestation {
     use super::*;
+    use crate::{
+        Aaguid, AuthenticatorModel, CertificationLevel, Config, VerifiedAttestation, WebAuthnError,
+    };
+    use traits_authn::DeviceChallengeResponse;
 
     #[test]
     fn registration_fails_if_attestation_is_invalid() {
@@ -57,12 +61,452 @@ mod attestation {
             ));
         })
     }
+
+    #[test]
+    fn registration_fails_if_attestation_statement_does_not_match() {
+        new_test_ext(2, false).execute_with(|client| {
+            let (_, mut attestation) = client.attestation(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+
+            // Take the statement from a different registration
+            let other_attestation = client
+                .attestation::<()>(
+                    USER,
+                    System::block_number(),
+                    &UserAddress::get().encode(),
+                    AuthorityId::get(),
+                )
+                .1;
+            attestation.attestation_object = with_attestation_object_entry(
+                &attestation.attestation_object,
+                "attStmt",
+                attestation_object_entry(&other_attestation.attestation_object, "attStmt"),
+            );
+
+            assert_noop!(
+                Pass::register(RuntimeOrigin::root(), USER, attestation.clone()),
+                pallet_pass::Error::<Test>::DeviceAttestationInvalid,
+            );
+            assert_eq!(
+                attestation.verify().err(),
+                Some(WebAuthnError::InvalidAttestationStatement)
+            );
+        })
+    }
+
+    #[test]
+    fn registration_fails_if_public_key_is_not_attested() {
+        new_test_ext(2, false).execute_with(|client| {
+            let (_, mut attestation) = client.attestation(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+
+            // Claim the key of a different credential
+            attestation.public_key = client
+                .attestation::<()>(
+                    USER,
+                    System::block_number(),
+                    &UserAddress::get().encode(),
+                    AuthorityId::get(),
+                )
+                .1
+                .public_key;
+
+            assert_noop!(
+                Pass::register(RuntimeOrigin::root(), USER, attestation.clone()),
+                pallet_pass::Error::<Test>::DeviceAttestationInvalid,
+            );
+            assert_eq!(
+                attestation.verify().err(),
+                Some(WebAuthnError::PublicKeyMismatch)
+            );
+        })
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct HardwareBackedOnly;
+
+    impl Config for HardwareBackedOnly {
+        fn allow_attestation(attestation: &VerifiedAttestation) -> bool {
+            attestation.is_hardware_backed()
+        }
+
+        fn is_public_suffix(domain: &str) -> bool {
+            <()>::is_public_suffix(domain)
+        }
+    }
+
+    #[test]
+    fn runtime_can_require_hardware_backed_attestations() {
+        new_test_ext(2, false).execute_with(|client| {
+            let (_, attestation) = client.attestation::<()>(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+            let statement = attestation
+                .attestation_statement()
+                .expect("the software authenticator attests its credentials; qed");
+            assert!(!statement.trusted);
+            assert!(attestation.is_valid());
+
+            // A software authenticator is not hardware-backed
+            let (_, attestation) = client.attestation::<HardwareBackedOnly>(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+            assert!(!attestation.is_valid());
+            assert_eq!(
+                attestation.verify().err(),
+                Some(WebAuthnError::AttestationNotAllowed)
+            );
+        })
+    }
+
+    /// The mock authenticator has an empty AAGUID.
+    const MOCK_AAGUID: Aaguid = [0; 16];
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct KnownAuthenticators;
+
+    impl Config for KnownAuthenticators {
+        fn authenticator_model(aaguid: &Aaguid) -> Option<AuthenticatorModel> {
+            (*aaguid == MOCK_AAGUID).then_some(AuthenticatorModel {
+                certification: CertificationLevel::SelfAsserted,
+                revoked: false,
+            })
+        }
+
+        fn is_public_suffix(domain: &str) -> bool {
+            <()>::is_public_suffix(domain)
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct CertifiedOnly;
+
+    impl Config for CertifiedOnly {
+        fn authenticator_model(aaguid: &Aaguid) -> Option<AuthenticatorModel> {
+            KnownAuthenticators::authenticator_model(aaguid)
+        }
+
+        fn allow_authenticator(_: &Aaguid, model: Option<&AuthenticatorModel>) -> bool {
+            model.is_some_and(|model| model.certification >= CertificationLevel::L1)
+        }
+
+        fn is_public_suffix(domain: &str) -> bool {
+            <()>::is_public_suffix(domain)
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct RevokedAuthenticators;
+
+    impl Config for RevokedAuthenticators {
+        fn authenticator_model(_: &Aaguid) -> Option<AuthenticatorModel> {
+            Some(AuthenticatorModel {
+                certification: CertificationLevel::L2,
+                revoked: true,
+            })
+        }
+
+        fn is_public_suffix(domain: &str) -> bool {
+            <()>::is_public_suffix(domain)
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct DenyMockAuthenticator;
+
+    impl Config for DenyMockAuthenticator {
+        fn allow_authenticator(aaguid: &Aaguid, _: Option<&AuthenticatorModel>) -> bool {
+            *aaguid != MOCK_AAGUID
+        }
+
+        fn is_public_suffix(domain: &str) -> bool {
+            <()>::is_public_suffix(domain)
+        }
+    }
+
+    #[test]
+    fn runtime_can_allow_or_deny_authenticators_by_aaguid() {
+        new_test_ext(4, false).execute_with(|client| {
+            let (_, attestation) = client.attestation::<KnownAuthenticators>(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+            assert!(attestation.verify().is_ok());
+
+            let (_, attestation) = client.attestation::<CertifiedOnly>(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+            assert_eq!(
+                attestation.verify().err(),
+                Some(WebAuthnError::AuthenticatorNotAllowed)
+            );
+
+            let (_, attestation) = client.attestation::<RevokedAuthenticators>(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+            assert_eq!(
+                attestation.verify().err(),
+                Some(WebAuthnError::AuthenticatorNotAllowed)
+            );
+
+            let (_, attestation) = client.attestation::<DenyMockAuthenticator>(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+            assert_eq!(
+                attestation.verify().err(),
+                Some(WebAuthnError::AuthenticatorNotAllowed)
+            );
+        })
+    }
+
+    #[test]
+    fn devices_are_registered_under_the_hash_of_their_credential_id() {
+        new_test_ext(1, false).execute_with(|client| {
+            let (credential_id, mut attestation) = client.attestation::<()>(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+            assert_eq!(*attestation.device_id(), blake2_256(&credential_id));
+            assert!(attestation.verify().is_ok());
+
+            attestation.meta.device_id = [0; 32];
+            assert!(!attestation.is_valid());
+            assert_eq!(
+                attestation.verify().err(),
+                Some(WebAuthnError::DeviceIdMismatch)
+            );
+        })
+    }
+}
+
+mod attestation_statement {
+    use crate::{
+        runtime::attestation_statement::{parse_trust_anchors, verify},
+        AttestationFormat, AttestationType, VerifiedAttestation, MAX_CLIENT_DATA_LEN,
+    };
+
+    // Attestation objects for the same credential and `clientDataJSON`, whose certificates are
+    // valid from 2025 to 2049. See `fixtures/attestation/README.md`.
+    const CLIENT_DATA: &[u8] = br#"{"type":"webauthn.create","challenge":"ERERERERERERERERERERERERERERERERERERERERERE","origin":"https://pass.example.com","crossOrigin":false}"#;
+    const ROOT: &[u8] = include_bytes!("../fixtures/attestation/root.der");
+    const TPM_ROOT: &[u8] = include_bytes!("../fixtures/attestation/tpm-root.der");
+    const PACKED: &[u8] = include_bytes!("../fixtures/attestation/packed.cbor");
+    const PACKED_NON_CA_ISSUER: &[u8] =
+        include_bytes!("../fixtures/attestation/packed-non-ca-issuer.cbor");
+    const PACKED_INCOMPLETE_SUBJECT: &[u8] =
+        include_bytes!("../fixtures/attestation/packed-incomplete-subject.cbor");
+    const FIDO_U2F: &[u8] = include_bytes!("../fixtures/attestation/fido-u2f.cbor");
+    const APPLE: &[u8] = include_bytes!("../fixtures/attestation/apple.cbor");
+    const ANDROID_KEY: &[u8] = include_bytes!("../fixtures/attestation/android-key.cbor");
+    const TPM: &[u8] = include_bytes!("../fixtures/attestation/tpm.cbor");
+    const LONGEST_CHAIN_ROOT: &[u8] =
+        include_bytes!("../fixtures/attestation/longest-chain-root.der");
+    const TPM_LONGEST_CHAIN: &[u8] =
+        include_bytes!("../fixtures/attestation/tpm-longest-chain.cbor");
+    const TPM_CHAIN_TOO_LONG: &[u8] =
+        include_bytes!("../fixtures/attestation/tpm-chain-too-long.cbor");
+
+    /// 2030-01-01
+    const NOW: u64 = 1_893_456_000;
+
+    fn verify_fixture(
+        attestation_object: &[u8],
+        client_data: &[u8],
+        trust_anchors: &[&[u8]],
+        now: Option<u64>,
+    ) -> Option<VerifiedAttestation> {
+        let trust_anchors = trust_anchors
+            .iter()
+            .map(|anchor| anchor.to_vec())
+            .collect::<Vec<_>>();
+
+        verify(
+            attestation_object,
+            client_data,
+            &parse_trust_anchors(&trust_anchors),
+            now,
+        )
+    }
+
+    #[test]
+    fn every_format_verifies_against_its_trust_anchor() {
+        for (attestation_object, trust_anchor, format, attestation_type) in [
+            (
+                PACKED,
+                ROOT,
+                AttestationFormat::Packed,
+                AttestationType::Basic,
+            ),
+            (
+                FIDO_U2F,
+                ROOT,
+                AttestationFormat::FidoU2f,
+                AttestationType::Basic,
+            ),
+            (
+                APPLE,
+                ROOT,
+                AttestationFormat::Apple,
+                AttestationType::AnonCa,
+            ),
+            (
+                ANDROID_KEY,
+                ROOT,
+                AttestationFormat::AndroidKey,
+                AttestationType::Basic,
+            ),
+            (
+                TPM,
+                TPM_ROOT,
+                AttestationFormat::Tpm,
+                AttestationType::AttCa,
+            ),
+        ] {
+            assert_eq!(
+                verify_fixture(attestation_object, CLIENT_DATA, &[trust_anchor], Some(NOW)),
+                Some(VerifiedAttestation {
+                    format,
+                    attestation_type,
+                    trusted: true,
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn attestations_are_bound_to_the_client_data() {
+        let other_client_data = CLIENT_DATA.to_ascii_uppercase();
+        for attestation_object in [PACKED, FIDO_U2F, APPLE, ANDROID_KEY, TPM] {
+            assert_eq!(
+                verify_fixture(
+                    attestation_object,
+                    &other_client_data,
+                    &[ROOT, TPM_ROOT],
+                    None
+                ),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn chains_not_leading_to_a_trust_anchor_are_untrusted() {
+        for trust_anchors in [&[][..], &[TPM_ROOT]] {
+            assert_eq!(
+                verify_fixture(PACKED, CLIENT_DATA, trust_anchors, None),
+                Some(VerifiedAttestation {
+                    format: AttestationFormat::Packed,
+                    attestation_type: AttestationType::Basic,
+                    trusted: false,
+                })
+            );
+        }
+        // Malformed anchors are skipped
+        assert!(
+            verify_fixture(PACKED, CLIENT_DATA, &[&[1, 2, 3], ROOT], None)
+                .is_some_and(|attestation| attestation.trusted)
+        );
+    }
+
+    #[test]
+    fn issuers_must_be_certificate_authorities() {
+        // The attestation certificate is issued by an end-entity certificate of the root.
+        assert_eq!(
+            verify_fixture(PACKED_NON_CA_ISSUER, CLIENT_DATA, &[ROOT], None),
+            None
+        );
+    }
+
+    #[test]
+    fn packed_attestation_certificates_must_name_their_vendor() {
+        // The subject of the attestation certificate has no C, O nor CN. It chains to no trust
+        // anchor, so it would only be untrusted otherwise.
+        assert_eq!(
+            verify_fixture(PACKED_INCOMPLETE_SUBJECT, CLIENT_DATA, &[ROOT], None),
+            None
+        );
+    }
+
+    #[test]
+    fn trust_paths_are_bounded() {
+        // The `clientDataJSON` of the benchmarks, padded up to `MAX_CLIENT_DATA_LEN`.
+        let prefix = r#"{"type":"webauthn.create","challenge":"__________________________________________8","origin":"https://pass.example.com","crossOrigin":false,"padding":""#;
+        let padding = "a".repeat(MAX_CLIENT_DATA_LEN as usize - prefix.len() - 2);
+        let client_data = [prefix, &padding, r#""}"#].concat();
+
+        // `MAX_TRUST_PATH_LEN` certificates
+        assert_eq!(
+            verify_fixture(
+                TPM_LONGEST_CHAIN,
+                client_data.as_bytes(),
+                &[LONGEST_CHAIN_ROOT],
+                Some(NOW)
+            ),
+            Some(VerifiedAttestation {
+                format: AttestationFormat::Tpm,
+                attestation_type: AttestationType::AttCa,
+                trusted: true,
+            })
+        );
+        // One more CA
+        assert_eq!(
+            verify_fixture(
+                TPM_CHAIN_TOO_LONG,
+                client_data.as_bytes(),
+                &[LONGEST_CHAIN_ROOT],
+                Some(NOW)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn certificates_must_be_valid_at_the_given_time() {
+        // 2020-01-01 and 2060-01-01
+        for now in [1_577_836_800, 2_840_140_800] {
+            assert_eq!(
+                verify_fixture(PACKED, CLIENT_DATA, &[ROOT], Some(now)),
+                None
+            );
+        }
+    }
 }
 
 mod assertion {
     use super::*;
-    use frame::traits::TxBaseImplication;
-    use traits_authn::DeviceChallengeResponse;
+    use crate::{
+        BackupStatePolicy, Config, CredentialPublicKey, CredentialRecord, SignCountCheck,
+        SignCountPolicy, UserVerificationRequirement, WebAuthnError, CREDENTIAL_RECORD_VERSION,
+    };
+    use frame::{hashing::sha2_256, traits::TxBaseImplication};
+    use traits_authn::{util::VerifyCredential, DeviceChallengeResponse, UserChallengeResponse};
 
     #[test]
     fn authentication_fails_if_credentials_are_invalid() {
@@ -218,4 +662,1408 @@ mod assertion {
                 .map(|_| ()));
         })
     }
+
+    #[test]
+    fn authentication_fails_if_origin_does_not_match_rp_id() {
+        new_test_ext(2, false).execute_with(|client| {
+            let (credential_id, attestation) = client.attestation(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+
+            assert_ok!(Pass::register(
+                RuntimeOrigin::root(),
+                USER,
+                attestation.clone()
+            ));
+
+            let mut assertion = client.assertion::<()>(
+                credential_id,
+                System::block_number(),
+                &[],
+                AuthorityId::get(),
+            );
+            assert!(assertion.is_valid());
+
+            let c_data = String::from_utf8(assertion.client_data.into())
+                .map(|client_data| {
+                    client_data
+                        .replace("pass_web.pass.int", "evil.int")
+                        .as_bytes()
+                        .to_vec()
+                })
+                .expect("`client_data` is a buffer representation of a utf-8 encoded json");
+            assertion.client_data =
+                BoundedVec::try_from(c_data).expect("c_data is long enough; qed");
+
+            assert!(!assertion.is_valid());
+            assert_eq!(assertion.verify(), Err(WebAuthnError::RpIdMismatch));
+        })
+    }
+
+    #[test]
+    fn authentication_fails_if_cross_origin() {
+        new_test_ext(2, false).execute_with(|client| {
+            let (credential_id, _) = client.attestation::<()>(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+
+            let mut assertion = client.assertion::<()>(
+                credential_id,
+                System::block_number(),
+                &[],
+                AuthorityId::get(),
+            );
+            let mut client_data: serde_json::Map<String, serde_json::Value> =
+                serde_json::from_slice(&assertion.client_data)
+                    .expect("`client_data` is a JSON object; qed");
+            client_data.insert("crossOrigin".into(), true.into());
+            client_data.insert("topOrigin".into(), "https://partner.com".into());
+            assertion.client_data = BoundedVec::try_from(
+                serde_json::to_vec(&client_data).expect("`client_data` is serializable; qed"),
+            )
+            .expect("c_data is long enough; qed");
+
+            assert!(!assertion.is_valid());
+            assert_eq!(
+                assertion.verify(),
+                Err(WebAuthnError::CrossOriginNotAllowed)
+            );
+        })
+    }
+
+    #[test]
+    fn authentication_fails_if_user_handle_does_not_match() {
+        new_test_ext(2, false).execute_with(|client| {
+            let (credential_id, attestation) = client.attestation::<()>(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+            let authenticator_data = attestation
+                .authenticator_data()
+                .expect("attestations hold authenticator data; qed");
+            let record = CredentialRecord {
+                device_id: *attestation.device_id(),
+                public_key: attestation.public_key.clone(),
+                sign_count: 0,
+                backup_eligible: authenticator_data[32] & 0x08 != 0,
+                backup_state: false,
+                version: CREDENTIAL_RECORD_VERSION,
+                rp_id_hash: None,
+                reported_resident_key: None,
+                aaguid: None,
+                model: None,
+                credential_id: None,
+                transports: Default::default(),
+                created_at: None,
+                last_used_at: None,
+                user_handle: Some(attestation.user_handle),
+            };
+
+            let mut assertion = client.assertion::<()>(
+                credential_id,
+                System::block_number(),
+                &[],
+                AuthorityId::get(),
+            );
+            assert_eq!(assertion.user_handle, Some(USER));
+            assert_eq!(record.clone().verify_assertion(&assertion), Ok(()));
+
+            // The `userHandle` is optional.
+            assertion.user_handle = None;
+            assert_eq!(record.clone().verify_assertion(&assertion), Ok(()));
+
+            assertion.user_handle = Some([9; 32]);
+            assert_eq!(
+                record.clone().verify_assertion(&assertion),
+                Err(WebAuthnError::UserHandleMismatch)
+            );
+
+            // It's the `user.id` the credential was created with, not necessarily the user
+            assertion.meta.user_id = [9; 32];
+            assert_eq!(
+                CredentialRecord {
+                    user_handle: Some([9; 32]),
+                    ..record.clone()
+                }
+                .verify_assertion(&assertion),
+                Ok(())
+            );
+
+            // Records that don't know it expect the user the assertion is for
+            let legacy = CredentialRecord {
+                user_handle: None,
+                ..record
+            };
+            assert_eq!(legacy.clone().verify_assertion(&assertion), Ok(()));
+            assertion.meta.user_id = USER;
+            assert_eq!(
+                legacy.clone().verify_assertion(&assertion),
+                Err(WebAuthnError::UserHandleMismatch)
+            );
+        })
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct AlwaysVerifyUser;
+
+    impl Config for AlwaysVerifyUser {
+        fn authentication_user_verification() -> UserVerificationRequirement {
+            UserVerificationRequirement::Required
+        }
+
+        fn is_public_suffix(domain: &str) -> bool {
+            <()>::is_public_suffix(domain)
+        }
+    }
+
+    #[test]
+    fn authentication_can_require_user_verification() {
+        new_test_ext(3, false).execute_with(|client| {
+            let (credential_id, _) = client.attestation::<()>(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+
+            let mut assertion = client.assertion::<()>(
+                credential_id.clone(),
+                System::block_number(),
+                &[],
+                AuthorityId::get(),
+            );
+            // UP only
+            assertion.authenticator_data[32] &= !0b0000_0100;
+            assert!(assertion.is_valid());
+
+            let mut assertion = client.assertion::<AlwaysVerifyUser>(
+                credential_id,
+                System::block_number(),
+                &[],
+                AuthorityId::get(),
+            );
+            assert!(assertion.is_valid());
+            assertion.authenticator_data[32] &= !0b0000_0100;
+            assert!(!assertion.is_valid());
+            assert_eq!(assertion.verify(), Err(WebAuthnError::UserNotVerified));
+        })
+    }
+
+    #[test]
+    fn credentials_only_verify_for_their_rp_id() {
+        new_test_ext(2, false).execute_with(|client| {
+            let (credential_id, attestation) = client.attestation(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+            let assertion = client.assertion::<()>(
+                credential_id,
+                System::block_number(),
+                &[],
+                AuthorityId::get(),
+            );
+
+            let authenticator_data = attestation
+                .authenticator_data()
+                .expect("attestations hold authenticator data; qed");
+            let record = CredentialRecord {
+                device_id: *attestation.device_id(),
+                public_key: attestation.public_key.clone(),
+                sign_count: 0,
+                backup_eligible: authenticator_data[32] & 0x08 != 0,
+                backup_state: false,
+                version: CREDENTIAL_RECORD_VERSION,
+                rp_id_hash: authenticator_data[..32].try_into().ok(),
+                reported_resident_key: None,
+                aaguid: None,
+                model: None,
+                credential_id: None,
+                transports: Default::default(),
+                created_at: None,
+                last_used_at: None,
+                user_handle: None,
+            };
+            assert_eq!(record.clone().verify(&assertion), Some(()));
+
+            let mut other_rp = CredentialRecord {
+                rp_id_hash: Some(sha2_256(b"other.pass.int")),
+                ..record
+            };
+            assert_eq!(
+                other_rp.verify_assertion(&assertion),
+                Err(WebAuthnError::RpIdMismatch)
+            );
+        })
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct TrackBlocks;
+
+    impl Config for TrackBlocks {
+        fn block_number() -> Option<u32> {
+            System::block_number().try_into().ok()
+        }
+
+        fn is_public_suffix(domain: &str) -> bool {
+            <()>::is_public_suffix(domain)
+        }
+    }
+
+    #[test]
+    fn credentials_track_when_they_were_last_used() {
+        new_test_ext(2, false).execute_with(|client| {
+            let (credential_id, attestation) = client.attestation(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+            let assertion = client.assertion::<TrackBlocks>(
+                credential_id.clone(),
+                System::block_number(),
+                &[],
+                AuthorityId::get(),
+            );
+
+            let authenticator_data = attestation
+                .authenticator_data()
+                .expect("attestations hold authenticator data; qed");
+            let mut record = CredentialRecord {
+                device_id: *attestation.device_id(),
+                public_key: attestation.public_key.clone(),
+                sign_count: 0,
+                backup_eligible: authenticator_data[32] & 0x08 != 0,
+                backup_state: false,
+                version: CREDENTIAL_RECORD_VERSION,
+                rp_id_hash: None,
+                reported_resident_key: None,
+                aaguid: None,
+                model: None,
+                credential_id: None,
+                transports: Default::default(),
+                created_at: Some(0),
+                last_used_at: None,
+                user_handle: None,
+            };
+            let mut untracked = record.clone();
+            assert_ok!(record.verify_assertion(&assertion));
+            assert_eq!(record.created_at(), Some(0));
+            assert_eq!(record.last_used_at(), Some(1));
+
+            // Unless the runtime tells the current block
+            assert_ok!(untracked.verify_assertion(&client.assertion::<()>(
+                credential_id,
+                System::block_number(),
+                &[],
+                AuthorityId::get(),
+            )));
+            assert_eq!(untracked.last_used_at(), None);
+        })
+    }
+
+    #[test]
+    fn assertions_can_be_verified_with_prepared_keys() {
+        new_test_ext(2, false).execute_with(|client| {
+            let (credential_id, attestation) = client.attestation(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+            let assertion = client.assertion::<()>(
+                credential_id,
+                System::block_number(),
+                &[],
+                AuthorityId::get(),
+            );
+
+            let authenticator_data = attestation
+                .authenticator_data()
+                .expect("attestations hold authenticator data; qed");
+            let mut record = CredentialRecord {
+                device_id: *attestation.device_id(),
+                public_key: attestation.public_key.clone(),
+                sign_count: 0,
+                backup_eligible: authenticator_data[32] & 0x08 != 0,
+                backup_state: false,
+                version: CREDENTIAL_RECORD_VERSION,
+                rp_id_hash: None,
+                reported_resident_key: None,
+                aaguid: None,
+                model: None,
+                credential_id: None,
+                transports: Default::default(),
+                created_at: None,
+                last_used_at: None,
+                user_handle: None,
+            };
+            let public_key = record
+                .prepared_key()
+                .expect("the credential public key is valid; qed");
+            assert_ok!(record
+                .clone()
+                .verify_assertion_with_key(&assertion, &public_key));
+
+            // Keys of other credentials don't verify
+            // The Ed25519 base point
+            let mut other_key = [0x66; 44];
+            other_key[..13].copy_from_slice(&[
+                0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00, 0x58,
+            ]);
+            let other_key = CredentialPublicKey::EdDsa(other_key)
+                .prepare()
+                .expect("the key is valid; qed");
+            assert_eq!(
+                record.verify_assertion_with_key(&assertion, &other_key),
+                Err(WebAuthnError::InvalidSignature)
+            );
+        })
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct ReportClones;
+
+    std::thread_local! {
+        static POSSIBLE_CLONES: core::cell::RefCell<Vec<(u32, u32)>> = Default::default();
+    }
+
+    impl Config for ReportClones {
+        fn sign_count_policy() -> SignCountPolicy {
+            SignCountPolicy::Report
+        }
+
+        fn on_possible_clone(_: &traits_authn::DeviceId, stored: u32, received: u32) {
+            POSSIBLE_CLONES.with_borrow_mut(|clones| clones.push((stored, received)));
+        }
+
+        fn is_public_suffix(domain: &str) -> bool {
+            <()>::is_public_suffix(domain)
+        }
+    }
+
+    #[test]
+    fn possible_clones_are_detected() {
+        new_test_ext(3, true).execute_with(|client| {
+            let (credential_id, attestation) = client.attestation::<()>(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+            let authenticator_data = attestation
+                .authenticator_data()
+                .expect("attestations hold authenticator data; qed");
+            let record = CredentialRecord {
+                device_id: *attestation.device_id(),
+                public_key: attestation.public_key.clone(),
+                sign_count: 5,
+                backup_eligible: authenticator_data[32] & 0x08 != 0,
+                backup_state: false,
+                version: CREDENTIAL_RECORD_VERSION,
+                rp_id_hash: None,
+                reported_resident_key: None,
+                aaguid: None,
+                model: None,
+                credential_id: None,
+                transports: Default::default(),
+                created_at: None,
+                last_used_at: None,
+                user_handle: None,
+            };
+
+            let assertion = client.assertion::<()>(
+                credential_id.clone(),
+                System::block_number(),
+                &[],
+                AuthorityId::get(),
+            );
+            assert_eq!(
+                record.check_sign_count(1),
+                SignCountCheck::PossibleClone {
+                    stored: 5,
+                    received: 1
+                }
+            );
+            assert_eq!(
+                record.clone().verify_assertion(&assertion),
+                Err(WebAuthnError::PossibleClone)
+            );
+
+            let assertion = client.assertion::<ReportClones>(
+                credential_id,
+                System::block_number(),
+                &[],
+                AuthorityId::get(),
+            );
+            let mut reported = record.clone();
+            assert_eq!(reported.verify(&assertion), Some(()));
+            assert_eq!(reported.sign_count, 5);
+            assert_eq!(POSSIBLE_CLONES.with_borrow(Clone::clone), vec![(5, 2)]);
+        })
+    }
+
+    #[test]
+    fn zero_sign_counts_are_not_clones() {
+        let record = CredentialRecord {
+            device_id: [0; 32],
+            public_key: CredentialPublicKey::Es256([0; 91]),
+            sign_count: 0,
+            backup_eligible: false,
+            backup_state: false,
+            version: CREDENTIAL_RECORD_VERSION,
+            rp_id_hash: None,
+            reported_resident_key: None,
+            aaguid: None,
+            model: None,
+            credential_id: None,
+            transports: Default::default(),
+            created_at: None,
+            last_used_at: None,
+            user_handle: None,
+        };
+
+        assert_eq!(record.check_sign_count(0), SignCountCheck::Unsupported);
+        assert_eq!(record.check_sign_count(1), SignCountCheck::Increased);
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct ReportBackupStateChanges;
+
+    std::thread_local! {
+        static BACKUP_STATE_CHANGES: core::cell::RefCell<Vec<bool>> = Default::default();
+    }
+
+    impl Config for ReportBackupStateChanges {
+        fn on_backup_state_change(_: &traits_authn::DeviceId, backed_up: bool) {
+            BACKUP_STATE_CHANGES.with_borrow_mut(|changes| changes.push(backed_up));
+        }
+
+        fn is_public_suffix(domain: &str) -> bool {
+            <()>::is_public_suffix(domain)
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct NeverBackedUp;
+
+    impl Config for NeverBackedUp {
+        fn backup_state_policy() -> BackupStatePolicy {
+            BackupStatePolicy::NeverBackedUpAfterRegistration
+        }
+
+        fn is_public_suffix(domain: &str) -> bool {
+            <()>::is_public_suffix(domain)
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct SingleDevice;
+
+    impl Config for SingleDevice {
+        fn backup_state_policy() -> BackupStatePolicy {
+            BackupStatePolicy::SingleDevice
+        }
+
+        fn is_public_suffix(domain: &str) -> bool {
+            <()>::is_public_suffix(domain)
+        }
+    }
+
+    #[test]
+    fn backup_state_changes_follow_the_policy() {
+        new_test_ext(4, false).execute_with(|client| {
+            let (credential_id, attestation) = client.attestation::<()>(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+            let authenticator_data = attestation
+                .authenticator_data()
+                .expect("attestations hold authenticator data; qed");
+            let record = CredentialRecord {
+                device_id: *attestation.device_id(),
+                public_key: attestation.public_key.clone(),
+                sign_count: 0,
+                backup_eligible: authenticator_data[32] & 0x08 != 0,
+                backup_state: false,
+                version: CREDENTIAL_RECORD_VERSION,
+                rp_id_hash: None,
+                reported_resident_key: None,
+                aaguid: None,
+                model: None,
+                credential_id: None,
+                transports: Default::default(),
+                created_at: None,
+                last_used_at: None,
+                user_handle: None,
+            };
+
+            // Genuine changes are reported.
+            let assertion = client.assertion::<ReportBackupStateChanges>(
+                credential_id.clone(),
+                System::block_number(),
+                &[],
+                AuthorityId::get(),
+            );
+            let backed_up = assertion.authenticator_data[32] & 0x10 != 0;
+            let mut changed = CredentialRecord {
+                backup_state: !backed_up,
+                ..record.clone()
+            };
+            assert_ok!(changed.verify_assertion(&assertion));
+            assert_eq!(changed.backup_state, backed_up);
+            assert_eq!(
+                BACKUP_STATE_CHANGES.with_borrow(|changes| changes.clone()),
+                vec![backed_up]
+            );
+
+            // Credentials can't become backed up after registration.
+            let mut assertion = client.assertion::<NeverBackedUp>(
+                credential_id.clone(),
+                System::block_number(),
+                &[],
+                AuthorityId::get(),
+            );
+            assertion.authenticator_data[32] |= 0x10;
+            assert_eq!(
+                record.clone().verify_assertion(&assertion),
+                Err(WebAuthnError::BackedUpAfterRegistration)
+            );
+
+            // Backup eligible credentials are rejected.
+            let mut assertion = client.assertion::<SingleDevice>(
+                credential_id,
+                System::block_number(),
+                &[],
+                AuthorityId::get(),
+            );
+            assertion.authenticator_data[32] |= 0x08;
+            let mut backup_eligible = CredentialRecord {
+                backup_eligible: true,
+                ..record
+            };
+            assert_eq!(
+                backup_eligible.verify_assertion(&assertion),
+                Err(WebAuthnError::BackupEligibleNotAllowed)
+            );
+        })
+    }
+
+    #[test]
+    fn single_device_policies_reject_backup_eligible_registrations() {
+        new_test_ext(1, false).execute_with(|client| {
+            let (_, mut attestation) = client.attestation::<SingleDevice>(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+            let mut authenticator_data = attestation
+                .authenticator_data()
+                .expect("attestations hold authenticator data; qed");
+            authenticator_data[32] |= 0x08;
+            attestation.attestation_object = with_attestation_object_entry(
+                &attestation.attestation_object,
+                "authData",
+                coset::cbor::Value::Bytes(authenticator_data),
+            );
+            assert!(!attestation.is_valid());
+            assert_eq!(
+                attestation.verify().err(),
+                Some(WebAuthnError::BackupEligibleNotAllowed)
+            );
+        })
+    }
+}
+
+mod origins {
+    use super::*;
+    use crate::{
+        runtime::config::{check_cross_origin, rp_id_hash},
+        AllowedOrigin, Config, WebAuthnError,
+    };
+    use frame::hashing::sha2_256;
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct ExamplePass;
+
+    impl Config for ExamplePass {
+        fn allowed_origins(authority: &traits_authn::AuthorityId) -> Option<Vec<AllowedOrigin>> {
+            Some(if *authority == AuthorityId::get() {
+                vec![
+                    AllowedOrigin::Domain("example.com".into()),
+                    AllowedOrigin::AndroidApp("apk_key_hash".into()),
+                ]
+            } else {
+                vec![]
+            })
+        }
+
+        fn allowed_top_origins(authority: &traits_authn::AuthorityId) -> Vec<AllowedOrigin> {
+            if *authority == AuthorityId::get() {
+                vec![AllowedOrigin::Host("partner.com".into())]
+            } else {
+                vec![]
+            }
+        }
+
+        fn rp_id(_: &traits_authn::AuthorityId) -> Option<String> {
+            Some("example.com".into())
+        }
+
+        fn is_public_suffix(domain: &str) -> bool {
+            <()>::is_public_suffix(domain)
+        }
+    }
+
+    #[test]
+    fn any_origin_claims_its_host_by_default() {
+        assert_eq!(
+            rp_id_hash::<()>(&AuthorityId::get(), "https://pass_web.pass.int"),
+            Ok(sha2_256(b"pass_web.pass.int"))
+        );
+        assert_eq!(
+            rp_id_hash::<()>(&AuthorityId::get(), "android:apk-key-hash:apk_key_hash"),
+            Err(WebAuthnError::InvalidRpId)
+        );
+    }
+
+    #[test]
+    fn public_suffixes_are_not_rp_ids() {
+        assert_eq!(
+            rp_id_hash::<()>(&AuthorityId::get(), "https://com"),
+            Err(WebAuthnError::InvalidRpId)
+        );
+
+        // `()` can't tell registrable domains apart, runtimes can
+        assert_eq!(
+            rp_id_hash::<()>(&AuthorityId::get(), "https://co.uk"),
+            Ok(sha2_256(b"co.uk"))
+        );
+        assert_eq!(
+            rp_id_hash::<UkPass>(&AuthorityId::get(), "https://co.uk"),
+            Err(WebAuthnError::InvalidRpId)
+        );
+        assert_eq!(
+            rp_id_hash::<UkPass>(&AuthorityId::get(), "https://pass.co.uk"),
+            Ok(sha2_256(b"pass.co.uk"))
+        );
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct UkPass;
+
+    impl Config for UkPass {
+        fn is_public_suffix(domain: &str) -> bool {
+            matches!(domain, "uk" | "co.uk")
+        }
+    }
+
+    #[test]
+    fn origins_without_a_host_must_be_allowed_to_claim_the_rp_id() {
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        struct ExampleRpId;
+
+        impl Config for ExampleRpId {
+            fn rp_id(_: &traits_authn::AuthorityId) -> Option<String> {
+                Some("example.com".into())
+            }
+
+            fn is_public_suffix(domain: &str) -> bool {
+                <()>::is_public_suffix(domain)
+            }
+        }
+
+        assert_eq!(
+            rp_id_hash::<ExampleRpId>(&AuthorityId::get(), "https://pass.example.com"),
+            Ok(sha2_256(b"example.com"))
+        );
+        assert_eq!(
+            rp_id_hash::<ExampleRpId>(&AuthorityId::get(), "android:apk-key-hash:apk_key_hash"),
+            Err(WebAuthnError::OriginNotAllowed)
+        );
+    }
+
+    #[test]
+    fn allowed_origins_claim_the_configured_rp_id() {
+        let rp_id_hash_of = |origin| rp_id_hash::<ExamplePass>(&AuthorityId::get(), origin);
+
+        for origin in [
+            "https://example.com",
+            "https://pass.example.com",
+            "android:apk-key-hash:apk_key_hash",
+        ] {
+            assert_eq!(rp_id_hash_of(origin), Ok(sha2_256(b"example.com")));
+        }
+        for origin in [
+            "http://example.com",
+            "https://notexample.com",
+            "https://example.com.evil.com",
+            "android:apk-key-hash:other_apk_key_hash",
+        ] {
+            assert_eq!(rp_id_hash_of(origin), Err(WebAuthnError::OriginNotAllowed));
+        }
+    }
+
+    #[test]
+    fn cross_origin_ceremonies_are_rejected_by_default() {
+        assert_eq!(
+            check_cross_origin::<()>(&AuthorityId::get(), false, None),
+            Ok(())
+        );
+        assert_eq!(
+            check_cross_origin::<()>(&AuthorityId::get(), true, Some("https://partner.com")),
+            Err(WebAuthnError::CrossOriginNotAllowed)
+        );
+    }
+
+    #[test]
+    fn cross_origin_ceremonies_require_an_allowed_top_origin() {
+        let check = |authority, cross_origin, top_origin| {
+            check_cross_origin::<ExamplePass>(authority, cross_origin, top_origin)
+        };
+
+        assert_eq!(
+            check(&AuthorityId::get(), true, Some("https://partner.com")),
+            Ok(())
+        );
+        for (cross_origin, top_origin) in [
+            (true, None),
+            (true, Some("https://evil.com")),
+            (true, Some("http://partner.com")),
+            (false, Some("https://evil.com")),
+        ] {
+            assert_eq!(
+                check(&AuthorityId::get(), cross_origin, top_origin),
+                Err(WebAuthnError::CrossOriginNotAllowed)
+            );
+        }
+        assert_eq!(
+            check(&s("other_authority"), true, Some("https://partner.com")),
+            Err(WebAuthnError::CrossOriginNotAllowed)
+        );
+    }
+
+    #[test]
+    fn origins_are_scoped_to_authorities() {
+        assert_eq!(
+            rp_id_hash::<ExamplePass>(&s("other_authority"), "https://pass.example.com"),
+            Err(WebAuthnError::OriginNotAllowed)
+        );
+    }
+}
+
+mod authenticator_data {
+    use super::*;
+    use crate::runtime::authenticator_data::{AuthenticatorData, ParseError};
+
+    /// The authenticator data of a mock attestation, and where its credential public key is.
+    fn attested_authenticator_data() -> (Vec<u8>, usize) {
+        new_test_ext(1, false).execute_with(|client| {
+            let (credential_id, attestation) = client.attestation::<()>(
+                USER,
+                System::block_number(),
+                &UserAddress::get().encode(),
+                AuthorityId::get(),
+            );
+            (
+                attestation
+                    .authenticator_data()
+                    .expect("attestations hold authenticator data; qed"),
+                37 + 16 + 2 + credential_id.len(),
+            )
+        })
+    }
+
+    #[test]
+    fn trailing_data_is_rejected() {
+        let (authenticator_data, _) = attested_authenticator_data();
+        assert!(AuthenticatorData::try_from(authenticator_data.as_slice()).is_ok());
+
+        let mut trailing = authenticator_data.clone();
+        trailing.push(0);
+        assert!(matches!(
+            AuthenticatorData::try_from(trailing.as_slice()),
+            Err(ParseError::TrailingData)
+        ));
+
+        let mut assertion_data = authenticator_data[..37].to_vec();
+        assertion_data[32] &= !0x40;
+        assert!(AuthenticatorData::try_from(assertion_data.as_slice()).is_ok());
+        assertion_data.push(0);
+        assert!(matches!(
+            AuthenticatorData::try_from(assertion_data.as_slice()),
+            Err(ParseError::TrailingData)
+        ));
+    }
+
+    #[test]
+    fn non_canonical_cbor_is_measured_as_encoded() {
+        let (authenticator_data, key_offset) = attested_authenticator_data();
+        let entries = authenticator_data[key_offset] & 0x1f;
+        assert_eq!(authenticator_data[key_offset] & 0xe0, 0xa0);
+
+        // The same key, with a non-minimal map length, followed by indefinite-length
+        // extension outputs.
+        let mut non_canonical = authenticator_data[..key_offset].to_vec();
+        non_canonical[32] |= 0x80;
+        non_canonical.extend_from_slice(&[0xb9, 0, entries]);
+        non_canonical.extend_from_slice(&authenticator_data[key_offset + 1..]);
+        non_canonical
+            .extend_from_slice(&[0xbf, 0x7f, 0x62, b'a', b'b', 0x61, b'c', 0xff, 0xf5, 0xff]);
+
+        let parsed = AuthenticatorData::try_from(non_canonical.as_slice())
+            .expect("non-canonical CBOR is well-formed; qed");
+        let canonical = AuthenticatorData::try_from(authenticator_data.as_slice())
+            .expect("authenticator data is valid; qed");
+        assert_eq!(
+            parsed
+                .attested_credential_data
+                .map(|data| data.credential_public_key),
+            canonical
+                .attested_credential_data
+                .map(|data| data.credential_public_key)
+        );
+        assert!(parsed.extensions.is_some());
+    }
+
+    #[test]
+    fn malformed_cbor_is_rejected() {
+        let (authenticator_data, _) = attested_authenticator_data();
+        let with_extensions = |extensions: &[u8]| {
+            let mut data = authenticator_data.clone();
+            data[32] |= 0x80;
+            data.extend_from_slice(extensions);
+            AuthenticatorData::try_from(data.as_slice())
+        };
+
+        // Nested too deep
+        assert!(matches!(
+            with_extensions(&[[0x81; 100].as_slice(), &[0]].concat()),
+            Err(ParseError::MalformedCbor)
+        ));
+        // Lengths beyond the data
+        assert!(matches!(
+            with_extensions(&[0x9b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]),
+            Err(ParseError::Truncated)
+        ));
+        assert!(matches!(
+            with_extensions(&[0x5b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]),
+            Err(ParseError::Truncated)
+        ));
+        // A "break" out of place
+        assert!(matches!(
+            with_extensions(&[0xff]),
+            Err(ParseError::MalformedCbor)
+        ));
+    }
+}
+
+mod extensions {
+    use crate::{
+        runtime::extensions::verify, AuthenticatorExtensionOutputs, Config, CredProtect,
+        WebAuthnError,
+    };
+    use coset::cbor::Value;
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct ProtectedCredentials;
+
+    impl Config for ProtectedCredentials {
+        fn min_cred_protect() -> Option<CredProtect> {
+            Some(CredProtect::UserVerificationOptionalWithCredentialIdList)
+        }
+
+        fn requested_extensions() -> Option<Vec<&'static str>> {
+            Some(vec!["credProtect"])
+        }
+
+        fn is_public_suffix(domain: &str) -> bool {
+            <()>::is_public_suffix(domain)
+        }
+    }
+
+    fn outputs(outputs: &[(&str, Value)]) -> Value {
+        Value::Map(
+            outputs
+                .iter()
+                .map(|(identifier, output)| (Value::Text(identifier.to_string()), output.clone()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn outputs_are_parsed() {
+        let parsed: AuthenticatorExtensionOutputs = (&outputs(&[
+            ("credProtect", Value::Integer(3.into())),
+            ("hmac-secret", Value::Bool(true)),
+            ("credBlob", Value::Bool(false)),
+        ]))
+            .try_into()
+            .expect("outputs are well-formed; qed");
+
+        assert_eq!(
+            parsed.cred_protect,
+            Some(CredProtect::UserVerificationRequired)
+        );
+        assert_eq!(parsed.hmac_secret, Some(true));
+        assert_eq!(
+            parsed.identifiers,
+            vec!["credProtect", "hmac-secret", "credBlob"]
+        );
+
+        assert!(AuthenticatorExtensionOutputs::try_from(&outputs(&[(
+            "credProtect",
+            Value::Integer(4.into())
+        )]))
+        .is_err());
+    }
+
+    #[test]
+    fn registration_requires_min_cred_protect() {
+        assert_eq!(verify::<()>(None, true), Ok(()));
+        assert_eq!(
+            verify::<ProtectedCredentials>(None, true),
+            Err(WebAuthnError::InsufficientCredProtect)
+        );
+        assert_eq!(
+            verify::<ProtectedCredentials>(
+                Some(&outputs(&[("credProtect", Value::Integer(1.into()))])),
+                true
+            ),
+            Err(WebAuthnError::InsufficientCredProtect)
+        );
+        assert_eq!(
+            verify::<ProtectedCredentials>(
+                Some(&outputs(&[("credProtect", Value::Integer(2.into()))])),
+                true
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            verify::<()>(Some(&outputs(&[("credProtect", Value::Bool(true))])), true),
+            Err(WebAuthnError::InvalidExtensions)
+        );
+
+        // Assertions don't output `credProtect`
+        assert_eq!(verify::<ProtectedCredentials>(None, false), Ok(()));
+    }
+
+    #[test]
+    fn unrequested_extensions_are_rejected() {
+        let unrequested = outputs(&[("hmac-secret", Value::Bool(true))]);
+        assert_eq!(verify::<()>(Some(&unrequested), false), Ok(()));
+        assert_eq!(
+            verify::<ProtectedCredentials>(Some(&unrequested), false),
+            Err(WebAuthnError::UnrequestedExtension)
+        );
+    }
+}
+
+#[cfg(feature = "client")]
+mod client {
+    use super::*;
+    use crate::{
+        client::{device_id, CreationOptions, Error},
+        Assertion, Attestation, AuthenticatorTransport, CredentialPublicKey,
+    };
+    use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+    use traits_authn::Challenger;
+
+    fn b64(bytes: &[u8]) -> String {
+        base64::encode_engine(bytes, &BASE64_URL_SAFE_NO_PAD)
+    }
+
+    #[test]
+    fn attestations_are_built_from_registration_json() {
+        new_test_ext(1, false).execute_with(|client| {
+            let challenge =
+                BlockChallenger::generate(&System::block_number(), &UserAddress::get().encode());
+            let (credential_id, authenticator_data, client_data, public_key, attestation_object) =
+                client
+                    .create_credential_sync(USER, challenge.as_slice())
+                    .expect("Failed creating credential");
+
+            let json = format!(
+                r#"{{
+                    "id": "{id}",
+                    "rawId": "{id}",
+                    "type": "public-key",
+                    "response": {{
+                        "clientDataJSON": "{}",
+                        "authenticatorData": "{}",
+                        "attestationObject": "{}",
+                        "publicKey": "{}",
+                        "publicKeyAlgorithm": -7,
+                        "transports": ["internal"]
+                    }},
+                    "clientExtensionResults": {{ "credProps": {{ "rk": true }} }}
+                }}"#,
+                b64(&client_data),
+                b64(&authenticator_data),
+                b64(&attestation_object),
+                b64(&public_key),
+                id = b64(&credential_id),
+            );
+
+            let attestation: Attestation<_> = Attestation::from_registration_json(
+                &json,
+                AuthorityId::get(),
+                USER,
+                System::block_number(),
+            )
+            .expect("registration JSON is valid; qed");
+            assert_eq!(attestation.meta.device_id, device_id(&credential_id));
+            assert_eq!(
+                attestation.public_key,
+                CredentialPublicKey::Es256(public_key)
+            );
+            assert_eq!(attestation.attestation_object, attestation_object);
+            assert_eq!(attestation.user_handle, USER);
+            assert_eq!(attestation.client_extensions.resident_key, Some(true));
+            assert_eq!(
+                attestation.transports.to_vec(),
+                vec![AuthenticatorTransport::Internal]
+            );
+
+            assert_ok!(Pass::register(RuntimeOrigin::root(), USER, attestation));
+        })
+    }
+
+    #[test]
+    fn assertions_are_built_from_authentication_json() {
+        let json = r#"{
+            "id": "AQID",
+            "rawId": "AQID",
+            "type": "public-key",
+            "response": {
+                "clientDataJSON": "e30",
+                "authenticatorData": "AAAA",
+                "signature": "BAUG"
+            },
+            "clientExtensionResults": {}
+        }"#;
+
+        let assertion: Assertion<u64> =
+            Assertion::from_authentication_json(json, AuthorityId::get(), USER, 1)
+                .expect("authentication JSON is valid; qed");
+        assert_eq!(assertion.client_data.to_vec(), b"{}".to_vec());
+        assert_eq!(assertion.authenticator_data, vec![0, 0, 0]);
+        assert_eq!(assertion.signature, vec![4, 5, 6]);
+        assert_eq!(assertion.user_handle, None);
+
+        assert_eq!(
+            Assertion::<u64>::from_authentication_json("{}", AuthorityId::get(), USER, 1),
+            Err(Error::InvalidJson)
+        );
+    }
+
+    #[test]
+    fn discoverable_assertions_are_built_for_their_user_handle() {
+        let json = |user_handle: &str| {
+            format!(
+                r#"{{
+                    "rawId": "AQID",
+                    "response": {{
+                        "clientDataJSON": "e30",
+                        "authenticatorData": "AAAA",
+                        "signature": "BAUG"
+                        {user_handle}
+                    }}
+                }}"#
+            )
+        };
+
+        let assertion: Assertion<u64> = Assertion::from_discoverable_authentication_json(
+            &json(&format!(r#", "userHandle": "{}""#, b64(&USER))),
+            AuthorityId::get(),
+            1,
+        )
+        .expect("authentication JSON is valid; qed");
+        assert_eq!(assertion.meta.user_id, USER);
+        assert_eq!(assertion.user_handle, Some(USER));
+
+        assert_eq!(
+            Assertion::<u64>::from_discoverable_authentication_json(
+                &json(""),
+                AuthorityId::get(),
+                1
+            ),
+            Err(Error::MissingUserHandle)
+        );
+        assert_eq!(
+            Assertion::<u64>::from_discoverable_authentication_json(
+                &json(r#", "userHandle": "AQID""#),
+                AuthorityId::get(),
+                1
+            ),
+            Err(Error::InvalidUserHandle)
+        );
+    }
+
+    #[test]
+    fn creation_options_carry_the_challenge() {
+        let options = CreationOptions::new("pass.int", &USER, "user", &[7; 32]).to_json();
+        assert!(options.contains(&format!(r#""challenge":"{}""#, b64(&[7; 32]))));
+        assert!(options.contains(r#""pubKeyCredParams":[{"type":"public-key","alg":-7}"#));
+    }
+}
+
+#[cfg(all(feature = "client", feature = "runtime"))]
+mod mds {
+    use crate::{
+        mds::{allowlist, payload_allowlist, Error},
+        AuthenticatorModel, CertificationLevel,
+    };
+    use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+
+    /// Signed by a test root, not the FIDO one. See `fixtures/mds`.
+    const BLOB: &str = include_str!("../fixtures/mds/blob.jwt");
+    const ROOT: &[u8] = include_bytes!("../fixtures/mds/root.der");
+    /// 2030-01-01, within the validity of the test certificates.
+    const NOW: u64 = 1_893_456_000;
+
+    fn blob(payload: &str) -> String {
+        base64::encode_engine(payload, &BASE64_URL_SAFE_NO_PAD)
+    }
+
+    #[test]
+    fn blobs_are_verified_against_the_fido_root() {
+        assert_eq!(
+            allowlist(BLOB, ROOT, NOW),
+            Ok(vec![(
+                [
+                    0xee, 0x88, 0x28, 0x79, 0x72, 0x1c, 0x49, 0x13, 0x97, 0x75, 0x3d, 0xfc, 0xce,
+                    0x97, 0x07, 0x2a
+                ],
+                AuthenticatorModel {
+                    certification: CertificationLevel::L1,
+                    revoked: false,
+                }
+            )])
+        );
+
+        // Another root
+        assert_eq!(
+            allowlist(
+                BLOB,
+                include_bytes!("../fixtures/attestation/root.der"),
+                NOW
+            ),
+            Err(Error::UntrustedChain)
+        );
+        assert_eq!(allowlist(BLOB, &ROOT[1..], NOW), Err(Error::InvalidRoot));
+        // Before the certificates were issued
+        assert_eq!(
+            allowlist(BLOB, ROOT, 1_577_836_800),
+            Err(Error::UntrustedChain)
+        );
+    }
+
+    #[test]
+    fn tampered_blobs_are_rejected() {
+        let [header, _, signature]: [&str; 3] = BLOB
+            .split('.')
+            .collect::<Vec<_>>()
+            .try_into()
+            .expect("the fixture is a JWT; qed");
+        let tampered = format!("{header}.{}.{signature}", blob(r#"{ "entries": [] }"#));
+        assert_eq!(
+            allowlist(&tampered, ROOT, NOW),
+            Err(Error::InvalidSignature)
+        );
+
+        let unsigned = format!("{}.{}.{signature}", blob(r#"{"alg":"none"}"#), blob("{}"));
+        assert_eq!(allowlist(&unsigned, ROOT, NOW), Err(Error::InvalidHeader));
+    }
+
+    #[test]
+    fn allowlists_are_built_from_metadata_blobs() {
+        let payload = r#"{
+            "no": 1,
+            "entries": [
+                {
+                    "aaguid": "ee882879-721c-4913-9775-3dfcce97072a",
+                    "statusReports": [
+                        { "status": "FIDO_CERTIFIED_L1" },
+                        { "status": "FIDO_CERTIFIED_L2" }
+                    ]
+                },
+                {
+                    "attestationCertificateKeyIdentifiers": ["bf7f8a4ad9e4a5b0e3ef50aa2dc9fb0c8b0ac1c7"],
+                    "statusReports": [{ "status": "FIDO_CERTIFIED" }]
+                },
+                {
+                    "aaguid": "00000000-0000-0000-0000-000000000001",
+                    "statusReports": [
+                        { "status": "FIDO_CERTIFIED" },
+                        { "status": "USER_VERIFICATION_BYPASS" }
+                    ]
+                }
+            ]
+        }"#;
+
+        // U2F authenticators (without AAGUID) are skipped, and entries are sorted.
+        assert_eq!(
+            payload_allowlist(&blob(payload)),
+            Ok(vec![
+                (
+                    [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+                    AuthenticatorModel {
+                        certification: CertificationLevel::L1,
+                        revoked: true,
+                    }
+                ),
+                (
+                    [
+                        0xee, 0x88, 0x28, 0x79, 0x72, 0x1c, 0x49, 0x13, 0x97, 0x75, 0x3d, 0xfc,
+                        0xce, 0x97, 0x07, 0x2a
+                    ],
+                    AuthenticatorModel {
+                        certification: CertificationLevel::L2,
+                        revoked: false,
+                    }
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn the_latest_status_report_counts() {
+        let payload = r#"{
+            "entries": [
+                {
+                    "aaguid": "00000000-0000-0000-0000-000000000001",
+                    "statusReports": [
+                        { "status": "FIDO_CERTIFIED_L2", "effectiveDate": "2023-03-01" },
+                        { "status": "USER_VERIFICATION_BYPASS", "effectiveDate": "2022-06-01" },
+                        { "status": "FIDO_CERTIFIED_L1", "effectiveDate": "2022-01-01" }
+                    ]
+                },
+                {
+                    "aaguid": "00000000-0000-0000-0000-000000000002",
+                    "statusReports": [
+                        { "status": "FIDO_CERTIFIED_L2", "effectiveDate": "2022-01-01" },
+                        { "status": "FIDO_CERTIFIED_L1", "effectiveDate": "2023-01-01" },
+                        { "status": "REVOKED", "effectiveDate": "2024-01-01" }
+                    ]
+                }
+            ]
+        }"#;
+
+        // A fix re-certifies a model, and certifications can be downgraded.
+        assert_eq!(
+            payload_allowlist(&blob(payload)),
+            Ok(vec![
+                (
+                    [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+                    AuthenticatorModel {
+                        certification: CertificationLevel::L2,
+                        revoked: false,
+                    }
+                ),
+                (
+                    [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2],
+                    AuthenticatorModel {
+                        certification: CertificationLevel::L1,
+                        revoked: true,
+                    }
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn malformed_blobs_are_rejected() {
+        assert_eq!(allowlist("e30.e30", ROOT, NOW), Err(Error::MalformedJwt));
+        assert_eq!(
+            allowlist("!!.e30.c2ln", ROOT, NOW),
+            Err(Error::InvalidBase64)
+        );
+        assert_eq!(payload_allowlist("!!"), Err(Error::InvalidBase64));
+        assert_eq!(payload_allowlist(&blob("{}")), Err(Error::InvalidJson));
+        assert_eq!(
+            payload_allowlist(&blob(r#"{ "entries": [{ "aaguid": "not-an-aaguid" }] }"#)),
+            Err(Error::InvalidAaguid)
+        );
+    }
+}
+
+mod credential_record {
+    use super::*;
+    use crate::{
+        Algorithm, AuthenticatorTransport, CredentialPublicKey, CredentialRecord,
+        CREDENTIAL_RECORD_VERSION,
+    };
+
+    #[test]
+    fn legacy_records_decode_as_es256() {
+        let mut public_key = [0u8; 91];
+        public_key[0] = 0x30;
+        public_key[1] = 0x59;
+
+        // Records stored before keys were tagged with their algorithm.
+        let legacy_record = ([1u8; 32], public_key, 3u32, true, false).encode();
+
+        let record = CredentialRecord::decode(&mut legacy_record.as_slice())
+            .expect("legacy records can be decoded; qed");
+        assert_eq!(record.public_key, CredentialPublicKey::Es256(public_key));
+        assert_eq!(record.public_key.algorithm(), Algorithm::Es256);
+        assert_eq!(record.sign_count, 3);
+
+        // Once re-encoded, records carry the algorithm tag.
+        assert_eq!(
+            CredentialRecord::decode(&mut record.encode().as_slice())
+                .expect("records can be decoded; qed"),
+            record
+        );
+    }
+
+    #[test]
+    fn records_carry_their_version() {
+        let record = CredentialRecord {
+            device_id: [1; 32],
+            public_key: CredentialPublicKey::EdDsa([2; 44]),
+            sign_count: 3,
+            backup_eligible: true,
+            backup_state: false,
+            version: CREDENTIAL_RECORD_VERSION,
+            rp_id_hash: Some([4; 32]),
+            reported_resident_key: Some(true),
+            aaguid: Some([5; 16]),
+            model: None,
+            credential_id: Some(
+                vec![3; 64]
+                    .try_into()
+                    .expect("the credential ID is short enough; qed"),
+            ),
+            transports: vec![AuthenticatorTransport::Usb, AuthenticatorTransport::Nfc]
+                .try_into()
+                .expect("there are only two transports; qed"),
+            created_at: Some(7),
+            last_used_at: Some(9),
+            user_handle: Some([6; 32]),
+        };
+        let encoded = record.encode();
+        assert_eq!(encoded[32 + 45 + 4 + 2], CREDENTIAL_RECORD_VERSION);
+        assert_eq!(
+            CredentialRecord::decode(&mut encoded.as_slice()).expect("records can be decoded; qed"),
+            record
+        );
+
+        let mut unknown_version = encoded;
+        unknown_version[32 + 45 + 4 + 2] = CREDENTIAL_RECORD_VERSION + 1;
+        assert!(CredentialRecord::decode(&mut unknown_version.as_slice()).is_err());
+    }
 }
@@ -0,0 +1,24 @@
+//! Weights of the WebAuthn authenticator, out of its `runtime-benchmarks` suite.
+
+use frame::prelude::Weight;
+
+/// Weight functions of the WebAuthn authenticator, so composite authenticators can charge the
+/// cost of each of their variants.
+pub trait WeightInfo {
+    /// Verifying an [`Attestation`](crate::Attestation), including its attestation statement.
+    fn register() -> Weight;
+    /// Verifying an [`Assertion`](crate::Assertion) against its credential.
+    fn authenticate() -> Weight;
+}
+
+/// Estimates, until runtimes benchmark their own hardware. They assume P-256 signatures are
+/// verified in the runtime, rather than with the `host-p256` host function.
+impl WeightInfo for () {
+    fn register() -> Weight {
+        Weight::from_parts(6_000_000_000, 0)
+    }
+
+    fn authenticate() -> Weight {
+        Weight::from_parts(5_000_000_000, 0)
+    }
+}
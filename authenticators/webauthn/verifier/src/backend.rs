@@ -0,0 +1,73 @@
+//! Backends for ECDSA P-256 verification, which most passkeys sign with.
+//!
+//! [`PureRust`] verifies with the `p256` crate, which is slow when compiled to Wasm. With the
+//! `host-p256` feature, [`HostFunction`] calls the `p256_crypto` host function instead, which
+//! nodes must provide by registering [`p256_crypto::HostFunctions`]. Either one is the
+//! [`DefaultBackend`], and both give the same results.
+
+use p256::ecdsa::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey};
+
+/// Verifies ECDSA P-256 signatures.
+pub trait P256Backend {
+    /// Verifies a `signature` of the SHA-256 `prehash` of a message.
+    fn verify_prehash(public_key: &VerifyingKey, prehash: &[u8; 32], signature: &Signature)
+        -> bool;
+}
+
+/// Verifies signatures in pure Rust, with the `p256` crate.
+pub struct PureRust;
+
+impl P256Backend for PureRust {
+    fn verify_prehash(
+        public_key: &VerifyingKey,
+        prehash: &[u8; 32],
+        signature: &Signature,
+    ) -> bool {
+        public_key.verify_prehash(prehash, signature).is_ok()
+    }
+}
+
+/// Verifies signatures by calling the `p256_crypto` host function.
+#[cfg(feature = "host-p256")]
+pub struct HostFunction;
+
+#[cfg(feature = "host-p256")]
+impl P256Backend for HostFunction {
+    fn verify_prehash(
+        public_key: &VerifyingKey,
+        prehash: &[u8; 32],
+        signature: &Signature,
+    ) -> bool {
+        p256_crypto::verify_prehash(
+            public_key.to_encoded_point(true).as_bytes(),
+            prehash,
+            &signature.to_bytes(),
+        )
+    }
+}
+
+#[cfg(not(feature = "host-p256"))]
+pub type DefaultBackend = PureRust;
+#[cfg(feature = "host-p256")]
+pub type DefaultBackend = HostFunction;
+
+/// Host functions for ECDSA P-256, run natively by the node.
+#[cfg(feature = "host-p256")]
+#[sp_runtime_interface::runtime_interface]
+pub trait P256Crypto {
+    /// Verifies a `signature` (`r || s`) of a SHA-256 `prehash` by a SEC1-encoded
+    /// `public_key`.
+    fn verify_prehash(
+        public_key: sp_runtime_interface::pass_by::PassFatPointerAndRead<&[u8]>,
+        prehash: sp_runtime_interface::pass_by::PassFatPointerAndRead<&[u8]>,
+        signature: sp_runtime_interface::pass_by::PassFatPointerAndRead<&[u8]>,
+    ) -> bool {
+        let Ok(public_key) = VerifyingKey::from_sec1_bytes(public_key) else {
+            return false;
+        };
+        let Ok(signature) = Signature::from_slice(signature) else {
+            return false;
+        };
+        public_key.verify_prehash(prehash, &signature).is_ok()
+    }
+}
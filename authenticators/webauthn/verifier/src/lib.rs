@@ -1,26 +1,27 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-//! Verifies a WebAuthn response signature.
+//! Verifies WebAuthn assertion signatures.
 //!
-//! This function validates the signature of a WebAuthn authentication response by:
+//! Authenticators sign the binary concatenation of the `authenticatorData` and the SHA-256 hash
+//! of the `clientDataJSON`, which [`webauthn_verify`] and its variants rebuild and verify
+//! against the credential public key. [`verify_signature`] verifies signatures over any other
+//! message, like those of attestation statements.
 //!
-//! 1. Concatenating the `authenticator_data` and the hashed `client_data_json` to form the message.
-//! 2. Verifying the `signature_der` against the message using the provided `credential_public_key_cbor`.
+//! Public keys are DER-encoded `SubjectPublicKeyInfo`s, as returned by
+//! `AuthenticatorAttestationResponse.getPublicKey()`. The following COSE algorithms are
+//! supported (see [`Algorithm`]):
 //!
-//! The `credential_public_key_cbor` should be in COSE format and correspond to an ECDSA P-256 public key,
-//! as specified in the WebAuthn standard.
+//! * `-7` (ES256): ECDSA over P-256 with SHA-256. The signature is DER-encoded.
+//! * `-8` (EdDSA): Ed25519. The signature is the raw 64-byte signature.
+//! * `-257` (RS256): RSASSA-PKCS1-v1_5 with SHA-256.
 //!
-//! # Arguments
+//! [`webauthn_verify`] assumes ES256; use [`webauthn_verify_with_algorithm`] for other algorithms.
 //!
-//! * `authenticator_data` - The raw bytes of the authenticator data provided by the authenticator.
-//! * `client_data_json` - The client data JSON.
-//! * `signature_der` - The signature generated by the authenticator.
-//! * `credential_public_key_cbor` - The public key in COSE format extracted from the authenticator's attestation data.
+//! Verifiers of many assertions can decode each credential public key once, as a [`PreparedKey`],
+//! and verify them with [`webauthn_verify_prepared`], or all at once with [`webauthn_verify_batch`].
 //!
-//! # Returns
-//!
-//! * `true` if the signature is valid.
-//! * `false` if the signature is invalid.
+//! ES256 signatures are verified by a [`P256Backend`]: in pure Rust by default, or with the
+//! `host-p256` feature, by a host function that nodes provide as [`p256_crypto::HostFunctions`].
 //!
 //! # Example
 //!
@@ -28,16 +29,14 @@
 //! let authenticator_data = /* ... */;
 //! let client_data_json = /* ... */;
 //! let signature_der = /* ... */;
-//! let credential_public_key_cbor = /* ... */;
+//! let credential_public_key_der = /* ... */;
 //!
-//! let is_valid = verify_webauthn_response(
+//! webauthn_verify(
 //!     &authenticator_data,
 //!     &client_data_json,
 //!     &signature_der,
-//!     &credential_public_key_cbor,
-//! );
-//!
-//! assert!(is_valid);
+//!     &credential_public_key_der,
+//! )?;
 //! ```
 //!
 //! # References
@@ -48,17 +47,24 @@
 //! * <https://www.w3.org/TR/webauthn/images/fido-signature-formats-figure2.svg>
 
 extern crate alloc;
+use alloc::vec::Vec;
 use p256::{
-    ecdsa::{signature::Verifier, DerSignature, VerifyingKey},
+    ecdsa::{signature::Verifier, Signature, VerifyingKey},
     elliptic_curve::PublicKey,
     pkcs8::DecodePublicKey,
     NistP256,
 };
+use rsa::{pkcs1v15, RsaPublicKey};
 use sha2::{Digest, Sha256};
 
+mod backend;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "host-p256")]
+pub use backend::{p256_crypto, HostFunction};
+pub use backend::{DefaultBackend, P256Backend, PureRust};
+
 #[derive(Debug)]
 pub enum VerifyError {
     ExtractPublicKey,
@@ -66,6 +72,40 @@ pub enum VerifyError {
     VerifySignature,
 }
 
+/// A COSE signature algorithm supported by the verifier.
+///
+/// See the [IANA COSE Algorithms registry](https://www.iana.org/assignments/cose/cose.xhtml#algorithms).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    /// ECDSA w/ SHA-256 over P-256 (`-7`).
+    Es256,
+    /// EdDSA over Ed25519 (`-8`).
+    EdDsa,
+    /// RSASSA-PKCS1-v1_5 using SHA-256 (`-257`).
+    Rs256,
+}
+
+impl Algorithm {
+    /// Returns the algorithm matching a COSE algorithm identifier, if supported.
+    pub const fn from_cose(alg: i64) -> Option<Self> {
+        match alg {
+            -7 => Some(Self::Es256),
+            -8 => Some(Self::EdDsa),
+            -257 => Some(Self::Rs256),
+            _ => None,
+        }
+    }
+
+    /// The COSE algorithm identifier.
+    pub const fn cose(&self) -> i64 {
+        match self {
+            Self::Es256 => -7,
+            Self::EdDsa => -8,
+            Self::Rs256 => -257,
+        }
+    }
+}
+
 const LOG_TARGET: &str = "webauthn_verifier::verify_signature";
 
 pub fn webauthn_verify(
@@ -74,44 +114,195 @@ pub fn webauthn_verify(
     signature_der: &[u8],
     credential_public_key_der: &[u8],
 ) -> Result<(), VerifyError> {
+    webauthn_verify_with_algorithm(
+        Algorithm::Es256,
+        authenticator_data,
+        client_data_json,
+        signature_der,
+        credential_public_key_der,
+    )
+}
+
+pub fn webauthn_verify_with_algorithm(
+    algorithm: Algorithm,
+    authenticator_data: &[u8],
+    client_data_json: &[u8],
+    signature: &[u8],
+    credential_public_key_der: &[u8],
+) -> Result<(), VerifyError> {
+    let public_key = PreparedKey::from_der(algorithm, credential_public_key_der)?;
+    webauthn_verify_prepared(&public_key, authenticator_data, client_data_json, signature)
+}
+
+/// Like [`webauthn_verify_with_algorithm`], with an already decoded public key.
+pub fn webauthn_verify_prepared(
+    public_key: &PreparedKey,
+    authenticator_data: &[u8],
+    client_data_json: &[u8],
+    signature: &[u8],
+) -> Result<(), VerifyError> {
+    let mut message = Vec::new();
+    public_key.verify(
+        signed_message(&mut message, authenticator_data, client_data_json),
+        signature,
+    )
+}
+
+/// The public key of an assertion in a batch.
+#[derive(Clone, Copy, Debug)]
+pub enum BatchKey<'a> {
+    /// A DER-encoded `SubjectPublicKeyInfo`, decoded for the assertion.
+    Der(Algorithm, &'a [u8]),
+    /// An already decoded key.
+    Prepared(&'a PreparedKey),
+}
+
+/// An assertion to verify with [`webauthn_verify_batch`].
+#[derive(Clone, Copy, Debug)]
+pub struct BatchItem<'a> {
+    pub authenticator_data: &'a [u8],
+    pub client_data_json: &'a [u8],
+    pub signature: &'a [u8],
+    pub public_key: BatchKey<'a>,
+}
+
+/// Verifies many assertions, building the messages they sign in a single shared buffer.
+/// Returns the result of each one, in order.
+pub fn webauthn_verify_batch<'a>(
+    items: impl IntoIterator<Item = BatchItem<'a>>,
+) -> Vec<Result<(), VerifyError>> {
+    let mut message = Vec::new();
+    items
+        .into_iter()
+        .map(|item| {
+            let message =
+                signed_message(&mut message, item.authenticator_data, item.client_data_json);
+            match item.public_key {
+                BatchKey::Der(algorithm, public_key_der) => {
+                    PreparedKey::from_der(algorithm, public_key_der)?
+                        .verify(message, item.signature)
+                }
+                BatchKey::Prepared(public_key) => public_key.verify(message, item.signature),
+            }
+        })
+        .collect()
+}
+
+/// Writes the message assertions sign into `buffer`.
+fn signed_message<'b>(
+    buffer: &'b mut Vec<u8>,
+    authenticator_data: &[u8],
+    client_data_json: &[u8],
+) -> &'b [u8] {
     // Step 1: Compute the SHA-256 hash of the client data JSON
     let client_data_hash: [u8; 32] = Sha256::digest(client_data_json).into();
 
     // Step 2: Concatenate authenticator data and client data hash
     log::trace!(target: LOG_TARGET, "Composing verify message");
-    let message = [authenticator_data, &client_data_hash].concat();
-
-    // Step 3: Extract public key from DER format
-    log::trace!(target: LOG_TARGET, "Obtaining public key");
-    let public_key: PublicKey<NistP256> =
-        DecodePublicKey::from_public_key_der(credential_public_key_der).map_err(|e| {
-            log::error!(target: LOG_TARGET, "WebAuthn verification failed with ExtractPublicKey error, reason={}", e);
-            VerifyError::ExtractPublicKey
-        })?;
-
-    let verifying_key = VerifyingKey::from(public_key);
-
-    // Step 4: Parse the DER signature
-    log::trace!(target: LOG_TARGET, "Parsing signature");
-    let signature =
-        DerSignature::try_from(signature_der).map_err(|e| {
-            log::error!(target: LOG_TARGET, "WebAuthn verification failed with ParseSignature error, reason={}", e);
-            VerifyError::ParseSignature
-        })?;
-
-    log::trace!(
-        target: LOG_TARGET,
-        "Run WebAuthn verify_signature: message={:?}, public_key={:?}, signature={:?}",
-        &message,
-        &public_key,
-        &signature
-    );
-    // Step 5: Verify the signature
-    verifying_key
-        .verify(&message, &signature)
-        .map(|_| ())
-        .map_err(|e| {
-            log::error!(target: LOG_TARGET, "WebAuthn verification failed with VerifySignature error, reason={}", e);
-            VerifyError::VerifySignature
-        })
+    buffer.clear();
+    buffer.extend_from_slice(authenticator_data);
+    buffer.extend_from_slice(&client_data_hash);
+    buffer
+}
+
+/// Verifies a `signature` over an arbitrary `message`, using a DER-encoded public key.
+///
+/// Besides assertions, this is used to check signatures in attestation statements, whose signed
+/// message depends on the attestation format.
+pub fn verify_signature(
+    algorithm: Algorithm,
+    message: &[u8],
+    signature: &[u8],
+    public_key_der: &[u8],
+) -> Result<(), VerifyError> {
+    PreparedKey::from_der(algorithm, public_key_der)?.verify(message, signature)
+}
+
+/// A credential public key, decoded once to verify any number of signatures.
+///
+/// Decoding is a good part of the cost of verifying a signature, so verifiers of many assertions
+/// of the same credential should keep it around.
+#[derive(Clone, Debug)]
+pub enum PreparedKey {
+    Es256(VerifyingKey),
+    EdDsa(ed25519_dalek::VerifyingKey),
+    Rs256(pkcs1v15::VerifyingKey<Sha256>),
+}
+
+impl PreparedKey {
+    /// Decodes a DER-encoded `SubjectPublicKeyInfo` of an `algorithm` key.
+    pub fn from_der(algorithm: Algorithm, public_key_der: &[u8]) -> Result<Self, VerifyError> {
+        // Step 3: Extract public key from DER format
+        log::trace!(target: LOG_TARGET, "Obtaining {algorithm:?} public key");
+        match algorithm {
+            Algorithm::Es256 => PublicKey::<NistP256>::from_public_key_der(public_key_der)
+                .map(|public_key| Self::Es256(VerifyingKey::from(public_key)))
+                .map_err(failed_with(VerifyError::ExtractPublicKey)),
+            Algorithm::EdDsa => ed25519_dalek::VerifyingKey::from_public_key_der(public_key_der)
+                .map(Self::EdDsa)
+                .map_err(failed_with(VerifyError::ExtractPublicKey)),
+            Algorithm::Rs256 => RsaPublicKey::from_public_key_der(public_key_der)
+                .map(|public_key| Self::Rs256(pkcs1v15::VerifyingKey::new(public_key)))
+                .map_err(failed_with(VerifyError::ExtractPublicKey)),
+        }
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        match self {
+            Self::Es256(_) => Algorithm::Es256,
+            Self::EdDsa(_) => Algorithm::EdDsa,
+            Self::Rs256(_) => Algorithm::Rs256,
+        }
+    }
+
+    /// Verifies a `signature` over an arbitrary `message`.
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), VerifyError> {
+        self.verify_with::<DefaultBackend>(message, signature)
+    }
+
+    /// Like [`verify`](Self::verify), with ES256 signatures verified by the `B` backend.
+    pub fn verify_with<B: P256Backend>(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), VerifyError> {
+        log::trace!(target: LOG_TARGET, "Verifying {:?} signature", self.algorithm());
+        // Steps 4 and 5: Parse the signature and verify it
+        match self {
+            Self::Es256(verifying_key) => {
+                let signature = Signature::from_der(signature)
+                    .map_err(failed_with(VerifyError::ParseSignature))?;
+                let prehash: [u8; 32] = Sha256::digest(message).into();
+                B::verify_prehash(verifying_key, &prehash, &signature)
+                    .then_some(())
+                    .ok_or(VerifyError::VerifySignature)
+                    .inspect_err(|error| {
+                        log::debug!(target: LOG_TARGET, "WebAuthn verification failed with {error:?} error");
+                    })
+            }
+            Self::EdDsa(verifying_key) => {
+                let signature = ed25519_dalek::Signature::from_slice(signature)
+                    .map_err(failed_with(VerifyError::ParseSignature))?;
+                verifying_key
+                    .verify_strict(message, &signature)
+                    .map_err(failed_with(VerifyError::VerifySignature))
+            }
+            Self::Rs256(verifying_key) => {
+                let signature = pkcs1v15::Signature::try_from(signature)
+                    .map_err(failed_with(VerifyError::ParseSignature))?;
+                verifying_key
+                    .verify(message, &signature)
+                    .map_err(failed_with(VerifyError::VerifySignature))
+            }
+        }
+    }
+}
+
+/// Logs why verification failed, as `error`. Invalid signatures are expected input, so they're
+/// logged at `debug`.
+fn failed_with<E: core::fmt::Display>(error: VerifyError) -> impl FnOnce(E) -> VerifyError {
+    move |reason| {
+        log::debug!(target: LOG_TARGET, "WebAuthn verification failed with {error:?} error, reason={reason}");
+        error
+    }
 }
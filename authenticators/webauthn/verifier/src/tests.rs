@@ -1,6 +1,6 @@
 use super::*;
 use coset::{
-    iana::{Algorithm, EllipticCurve},
+    iana::{Algorithm as CoseAlgorithm, EllipticCurve},
     CoseKeyBuilder,
 };
 use p256::ecdsa::{signature::Signer, Signature, SigningKey};
@@ -29,7 +29,7 @@ fn test_verify_webauthn_response_with_generated_data() {
 
     // Step 3: Construct the COSE key pair
     let public_key_cose = CoseKeyBuilder::new_ec2_pub_key(EllipticCurve::P_256, x, y)
-        .algorithm(Algorithm::ES256)
+        .algorithm(CoseAlgorithm::ES256)
         .build();
 
     // Step 4: Convert to DER from COSE
@@ -81,7 +81,7 @@ fn test_verify_webauthn_response_with_invalid_signature() {
 
     // Step 3: Construct the COSE key pair
     let public_key_cose = CoseKeyBuilder::new_ec2_pub_key(EllipticCurve::P_256, x, y)
-        .algorithm(Algorithm::ES256)
+        .algorithm(CoseAlgorithm::ES256)
         .build();
 
     // Step 4: Convert to DER from COSE
@@ -117,3 +117,197 @@ fn test_verify_webauthn_response_with_invalid_signature() {
         "The signature verification should fail with an invalid signature."
     );
 }
+
+#[test]
+fn test_verify_webauthn_response_with_eddsa() {
+    use ed25519_dalek::{pkcs8::EncodePublicKey, Signer};
+
+    let authenticator_data = b"example authenticator data";
+    let client_data_json = br#"{
+        "challenge": "test-challenge",
+        "origin": "https://example.com",
+        "type": "webauthn.get"
+    }"#;
+
+    // Step 1: Generate an Ed25519 key pair
+    let private_key = ed25519_dalek::SigningKey::generate(&mut OsRng);
+    let public_key_der = private_key
+        .verifying_key()
+        .to_public_key_der()
+        .expect("Encoding public key as DER failed");
+
+    // Step 2: Sign the message to get the raw signature
+    let message = [
+        authenticator_data.as_slice(),
+        &Sha256::digest(client_data_json),
+    ]
+    .concat();
+    let signature = private_key.sign(&message).to_bytes();
+
+    // Step 3: Verify the signature
+    webauthn_verify_with_algorithm(
+        Algorithm::EdDsa,
+        authenticator_data,
+        client_data_json,
+        &signature,
+        public_key_der.as_bytes(),
+    )
+    .expect("Verifying signature failed");
+
+    // The same signature is rejected when verified as ES256
+    assert!(webauthn_verify(
+        authenticator_data,
+        client_data_json,
+        &signature,
+        public_key_der.as_bytes(),
+    )
+    .is_err());
+}
+
+#[test]
+fn test_verify_webauthn_response_with_rs256() {
+    use rsa::{
+        pkcs1v15::SigningKey as RsaSigningKey,
+        pkcs8::EncodePublicKey,
+        signature::{RandomizedSigner, SignatureEncoding},
+        RsaPrivateKey,
+    };
+
+    let authenticator_data = b"example authenticator data";
+    let client_data_json = br#"{
+        "challenge": "test-challenge",
+        "origin": "https://example.com",
+        "type": "webauthn.get"
+    }"#;
+
+    // Step 1: Generate a 2048-bit RSA key pair
+    let private_key = RsaPrivateKey::new(&mut OsRng, 2048).expect("Generating RSA key failed");
+    let public_key_der = private_key
+        .to_public_key()
+        .to_public_key_der()
+        .expect("Encoding public key as DER failed");
+
+    // Step 2: Sign the message using PKCS#1 v1.5 with SHA-256
+    let message = [
+        authenticator_data.as_slice(),
+        &Sha256::digest(client_data_json),
+    ]
+    .concat();
+    let signature = RsaSigningKey::<Sha256>::new(private_key)
+        .sign_with_rng(&mut OsRng, &message)
+        .to_vec();
+
+    // Step 3: Verify the signature
+    webauthn_verify_with_algorithm(
+        Algorithm::Rs256,
+        authenticator_data,
+        client_data_json,
+        &signature,
+        public_key_der.as_bytes(),
+    )
+    .expect("Verifying signature failed");
+
+    // Step 4: Tamper with the signature (should fail)
+    let mut tampered_signature = signature.clone();
+    tampered_signature[0] ^= 0xFF;
+    assert!(webauthn_verify_with_algorithm(
+        Algorithm::Rs256,
+        authenticator_data,
+        client_data_json,
+        &tampered_signature,
+        public_key_der.as_bytes(),
+    )
+    .is_err());
+}
+
+#[test]
+fn test_algorithm_from_cose() {
+    for algorithm in [Algorithm::Es256, Algorithm::EdDsa, Algorithm::Rs256] {
+        assert_eq!(Algorithm::from_cose(algorithm.cose()), Some(algorithm));
+    }
+    assert_eq!(Algorithm::from_cose(-35), None);
+}
+
+#[test]
+fn test_verify_webauthn_responses_in_batch() {
+    use ed25519_dalek::{pkcs8::EncodePublicKey, Signer as _};
+
+    let authenticator_data = b"example authenticator data";
+    let client_data_json = br#"{"type":"webauthn.get"}"#;
+    let message = [
+        authenticator_data.as_slice(),
+        &Sha256::digest(client_data_json),
+    ]
+    .concat();
+
+    // Step 1: Sign with an ES256 and an EdDSA key
+    let es256_key = SigningKey::random(&mut OsRng);
+    let es256_public_key = PreparedKey::Es256(*es256_key.verifying_key());
+    let es256_signature: Signature = es256_key.sign(&message);
+    let es256_signature = es256_signature.to_der();
+
+    let eddsa_key = ed25519_dalek::SigningKey::generate(&mut OsRng);
+    let eddsa_public_key_der = eddsa_key
+        .verifying_key()
+        .to_public_key_der()
+        .expect("Encoding public key as DER failed");
+    let eddsa_signature = eddsa_key.sign(&message).to_bytes();
+
+    // Step 2: Verify them, along with a tampered one
+    let item = |signature, public_key| BatchItem {
+        authenticator_data,
+        client_data_json,
+        signature,
+        public_key,
+    };
+    let results = webauthn_verify_batch([
+        item(
+            es256_signature.as_bytes(),
+            BatchKey::Prepared(&es256_public_key),
+        ),
+        item(
+            &eddsa_signature,
+            BatchKey::Der(Algorithm::EdDsa, eddsa_public_key_der.as_bytes()),
+        ),
+        item(
+            &[0; 64],
+            BatchKey::Der(Algorithm::EdDsa, eddsa_public_key_der.as_bytes()),
+        ),
+    ]);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_ok());
+    assert!(matches!(results[2], Err(VerifyError::VerifySignature)));
+
+    // Step 3: Prepared keys verify single assertions too
+    webauthn_verify_prepared(
+        &es256_public_key,
+        authenticator_data,
+        client_data_json,
+        es256_signature.as_bytes(),
+    )
+    .expect("Verifying signature failed");
+}
+
+#[cfg(feature = "host-p256")]
+#[test]
+fn test_p256_backends_verify_the_same() {
+    use p256::ecdsa::signature::hazmat::PrehashSigner;
+
+    let prehash: [u8; 32] = Sha256::digest(b"example message").into();
+    let signing_key = SigningKey::random(&mut OsRng);
+    let signature: Signature = signing_key.sign_prehash(&prehash).expect("Signing failed");
+    let mut tampered = prehash;
+    tampered[0] ^= 1;
+
+    for prehash in [prehash, tampered] {
+        assert_eq!(
+            PureRust::verify_prehash(signing_key.verifying_key(), &prehash, &signature),
+            HostFunction::verify_prehash(signing_key.verifying_key(), &prehash, &signature),
+        );
+    }
+    assert!(HostFunction::verify_prehash(
+        signing_key.verifying_key(),
+        &prehash,
+        &signature
+    ));
+}
@@ -63,11 +63,12 @@ fn make_eth_registration(
     let pair = EthKey::get();
     let address = eth_address_of(&pair);
     let signature = message.sign(&pair);
-    let attestation = PassDeviceAttestation::Eth(pass_ethereum::EthRegistration {
+    let attestation = PassDeviceAttestation::Eth(pass_ethereum::EthRegistration::new(
         address,
         message,
+        pass_ethereum::MessageMode::PersonalSign,
         signature,
-    });
+    ));
     (address, attestation)
 }
 
@@ -141,11 +142,12 @@ fn make_eth_credential(xtc: &impl ExtrinsicContext) -> (pass_ethereum::EthAddres
     let pair = EthKey::get();
     let address = eth_address_of(&pair);
     let signature = message.sign(&pair);
-    let credential = PassCredential::Eth(pass_ethereum::EthSignature {
-        user_id: ETH_USER,
+    let credential = PassCredential::Eth(pass_ethereum::EthSignature::new(
+        ETH_USER,
         message,
+        pass_ethereum::MessageMode::PersonalSign,
         signature,
-    });
+    ));
     (address, credential)
 }
 
@@ -192,11 +194,12 @@ mod cross_authenticator {
             let sol_pubkey = pass_solana::SolPubkey(SolKey::get().public().0);
             let ext = pallet_pass::PassAuthenticate::<Test>::from(
                 sol_pubkey.0,
-                PassCredential::Eth(pass_ethereum::EthSignature {
-                    user_id: SOL_USER,
-                    message: eth_msg,
-                    signature: eth_sig,
-                }),
+                PassCredential::Eth(pass_ethereum::EthSignature::new(
+                    SOL_USER,
+                    eth_msg,
+                    pass_ethereum::MessageMode::PersonalSign,
+                    eth_sig,
+                )),
             );
 
             assert_noop!(
@@ -470,11 +473,12 @@ mod authority {
                 PassPallet::register(
                     RuntimeOrigin::root(),
                     ETH_USER,
-                    PassDeviceAttestation::Eth(pass_ethereum::EthRegistration {
+                    PassDeviceAttestation::Eth(pass_ethereum::EthRegistration::new(
                         address,
                         message,
-                        signature,
-                    })
+                        pass_ethereum::MessageMode::PersonalSign,
+                        signature
+                    ))
                 ),
                 pallet_pass::Error::<Test>::DeviceAttestationInvalid,
             );